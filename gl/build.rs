@@ -14,6 +14,7 @@ fn main() {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    gl_generator::Source::bundled("gl.xml", khronos_api::GL_XML),
+                                    vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
 }
@@ -0,0 +1,188 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, BufReader};
+
+use khronos_api;
+
+use generators::{self, Generator, Options};
+use registry::{ExtensionSelector, Fallbacks, Filter, Ns, Profile, Registry, Version};
+use {Error, GlobalGenerator, Source, XML_DIR_VAR};
+
+/// The configuration of the bindings to generate.
+///
+/// ~~~no_run
+/// # extern crate gl_generator;
+/// # use gl_generator::{Bindings, Profile, StructGenerator, Version};
+/// # use gl_generator::registry::Ns;
+/// # fn main() {
+/// # let mut file = Vec::new();
+/// Bindings::new(Ns::Gl, Version::new(4, 5), Profile::Core)
+///     .extensions(vec!["GL_ARB_debug_output".to_string()])
+///     .generator(StructGenerator)
+///     .write_to(&mut file)
+///     .unwrap();
+/// # }
+/// ~~~
+///
+/// Everything but the API, the version and the profile has a default: the registry bundled with
+///  `khronos_api` for the API (see `Source::bundled`), no extensions, all the fallbacks, and the
+///  global generator without options.
+pub struct Bindings<'a, G = GlobalGenerator> {
+    ns: Ns,
+    version: Option<Version>,
+    profile: Profile,
+    source: Option<Source<'a>>,
+    extensions: ExtensionSelector,
+    fallbacks: Fallbacks,
+    generator: G,
+    options: Options,
+    rerun_if_changed: bool,
+}
+
+impl<'a> Bindings<'a> {
+    /// Starts the configuration of the bindings of a version of an API. A version of `None`
+    ///  selects the latest version that the registry defines.
+    ///
+    /// The profile is ignored by the APIs that don't have profiles, and replaced by the profile
+    ///  implied by pseudo-APIs like `Ns::Glcore`.
+    pub fn new<V>(ns: Ns, version: V, profile: Profile) -> Bindings<'a> where V: Into<Option<Version>> {
+        Bindings {
+            ns: ns,
+            version: version.into(),
+            profile: profile,
+            source: None,
+            extensions: ExtensionSelector::default(),
+            fallbacks: Fallbacks::All,
+            generator: GlobalGenerator,
+            options: Options::default(),
+            rerun_if_changed: false,
+        }
+    }
+}
+
+impl<'a, G> Bindings<'a, G> where G: Generator {
+    /// Sets the registry to generate the bindings from, like a newer `gl.xml`. Required for
+    ///  `Ns::Custom`.
+    pub fn source<S>(mut self, source: S) -> Bindings<'a, G> where S: Into<Source<'a>> {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the extensions to include, as a list of names or as an `ExtensionSelector`.
+    pub fn extensions<E>(mut self, extensions: E) -> Bindings<'a, G> where E: Into<ExtensionSelector> {
+        self.extensions = extensions.into();
+        self
+    }
+
+    /// Sets the fallbacks that the loaders try, like `Fallbacks::None` or a `FallbackPolicy`.
+    pub fn fallbacks<F>(mut self, fallbacks: F) -> Bindings<'a, G> where F: Into<Fallbacks> {
+        self.fallbacks = fallbacks.into();
+        self
+    }
+
+    /// Sets the generator that writes the bindings.
+    pub fn generator<H>(self, generator: H) -> Bindings<'a, H> where H: Generator {
+        Bindings {
+            ns: self.ns,
+            version: self.version,
+            profile: self.profile,
+            source: self.source,
+            extensions: self.extensions,
+            fallbacks: self.fallbacks,
+            generator: generator,
+            options: self.options,
+            rerun_if_changed: self.rerun_if_changed,
+        }
+    }
+
+    /// Sets the options of the generator.
+    pub fn options(mut self, options: Options) -> Bindings<'a, G> {
+        self.options = options;
+        self
+    }
+
+    /// Makes a build script tell Cargo to run it again when the registry file that is read
+    ///  changes, or when `GL_GENERATOR_XML_DIR` does if the bundled registry is used. Off by
+    ///  default.
+    ///
+    /// Once a build script prints a `cargo:rerun-if-changed` or `cargo:rerun-if-env-changed`
+    ///  line, Cargo only runs it again when the files and variables it names change, and not
+    ///  anymore when any file of the package does. A build script that enables this should print
+    ///  `cargo:rerun-if-changed` for itself and for the other files it depends on.
+    pub fn rerun_if_changed(mut self, enabled: bool) -> Bindings<'a, G> {
+        self.rerun_if_changed = enabled;
+        self
+    }
+
+    /// Reads and filters the registry, and checks that bindings can be generated from it,
+    ///  without writing anything.
+    ///
+    /// Returns an error if the registry could not be read or parsed, if the requested version or
+    ///  extensions are not available for the API, or if a command uses a type that can't be
+    ///  converted to Rust.
+    pub fn registry(&self) -> Result<Registry, Error> {
+        let filter = Filter {
+            api: self.ns.info().api.to_string(),
+            fallbacks: self.fallbacks.clone(),
+            extensions: self.extensions.clone(),
+            version: self.version,
+            profile: Some(self.ns.info().profile.unwrap_or(self.profile)),
+        };
+
+        let bundled;
+        let source = match self.source {
+            Some(ref source) => source,
+            None => {
+                bundled = try!(bundled_source(self.ns));
+                &bundled
+            },
+        };
+        if self.rerun_if_changed {
+            if self.source.is_none() {
+                println!("cargo:rerun-if-env-changed={}", XML_DIR_VAR);
+            }
+            if let Source::Path(ref path) = *source {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+        let source = try!(source.read());
+        let registry = try!(Registry::from_xml(BufReader::new(&source[..]), self.ns, Some(filter)));
+        try!(generators::check_types(&registry));
+        Ok(registry)
+    }
+
+    /// Generates the bindings and writes them to `dest`. On success, the names of the extensions
+    ///  that have been included in the bindings are returned.
+    ///
+    /// Nothing is written if the registry can't be read or filtered. See `registry`.
+    pub fn write_to<W>(&self, dest: &mut W) -> Result<Vec<String>, Error> where W: io::Write {
+        let registry = try!(self.registry());
+        try!(self.generator.write_with_options(&registry, self.ns, &self.options, dest));
+        Ok(registry.extensions.iter().map(|ext| ext.name.clone()).collect())
+    }
+}
+
+/// Returns the registry file of `khronos_api` that defines an API.
+fn bundled_source(ns: Ns) -> Result<Source<'static>, Error> {
+    match ns {
+        Ns::Gl | Ns::Gles1 | Ns::Gles2 | Ns::Glsc2 | Ns::Glcore =>
+            Ok(Source::bundled("gl.xml", khronos_api::GL_XML)),
+        Ns::Glx => Ok(Source::bundled("glx.xml", khronos_api::GLX_XML)),
+        Ns::Wgl => Ok(Source::bundled("wgl.xml", khronos_api::WGL_XML)),
+        Ns::Egl => Ok(Source::bundled("egl.xml", khronos_api::EGL_XML)),
+        Ns::Custom(info) => Err(Error::MissingSource(info.name.to_string())),
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of two filtered registries, for example GL 3.3 core and GL 4.5 core, or GL 4.5
+//!  and GLES 3.1.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use registry::{Binding, Cmd, Enum, Ns, Registry, Version};
+
+/// Lists the commands and enums that appear, disappear or change between two registries.
+///
+/// Commands and enums are matched by their identifier without prefix, so registries of
+///  different APIs can be compared. The reasons for additions and removals come from the
+///  provenance of `new`, which is only known if it has been filtered. `ns` is the API that `new`
+///  has been read for.
+pub fn diff(old: &Registry, new: &Registry, ns: Ns) -> Diff {
+    let old_cmds: HashMap<&str, &Cmd> = old.cmd_iter().map(|c| (&c.proto.ident[..], c)).collect();
+    let new_cmds: HashMap<&str, &Cmd> = new.cmd_iter().map(|c| (&c.proto.ident[..], c)).collect();
+    let old_enums: HashMap<&str, &Enum> = old.enum_iter().map(|e| (&e.ident[..], e)).collect();
+    let new_enums: HashMap<&str, &Enum> = new.enum_iter().map(|e| (&e.ident[..], e)).collect();
+
+    let mut cmds = Vec::new();
+    for (&ident, &cmd) in new_cmds.iter() {
+        match old_cmds.get(ident) {
+            None => cmds.push(added(new, ident)),
+            Some(&old_cmd) => {
+                let reasons = cmd_changes(old_cmd, cmd);
+                if !reasons.is_empty() {
+                    cmds.push(Change { ident: ident.to_string(), kind: ChangeKind::Changed, reasons: reasons });
+                }
+            }
+        }
+    }
+    cmds.extend(old_cmds.keys().filter(|ident| !new_cmds.contains_key(*ident))
+                               .map(|ident| removed(new, ns, ident)));
+
+    let mut enums = Vec::new();
+    for (&ident, &enm) in new_enums.iter() {
+        match old_enums.get(ident) {
+            None => enums.push(added(new, ident)),
+            Some(&old_enum) => {
+                let reasons = enum_changes(old_enum, enm);
+                if !reasons.is_empty() {
+                    enums.push(Change { ident: ident.to_string(), kind: ChangeKind::Changed, reasons: reasons });
+                }
+            }
+        }
+    }
+    enums.extend(old_enums.keys().filter(|ident| !new_enums.contains_key(*ident))
+                                 .map(|ident| removed(new, ns, ident)));
+
+    cmds.sort_by(|a, b| a.ident.cmp(&b.ident));
+    enums.sort_by(|a, b| a.ident.cmp(&b.ident));
+    Diff { cmds: cmds, enums: enums }
+}
+
+fn added(new: &Registry, ident: &str) -> Change {
+    let introduced_in = new.introduced_in(ident);
+    Change {
+        ident: ident.to_string(),
+        kind: ChangeKind::Added,
+        reasons: if introduced_in.is_empty() {
+            Vec::new()
+        } else {
+            vec![Reason::IntroducedIn(introduced_in.to_vec())]
+        },
+    }
+}
+
+fn removed(new: &Registry, ns: Ns, ident: &str) -> Change {
+    let mut reasons: Vec<Reason> = new.removed_in(ident).iter().map(|name| {
+        let feature = new.features.iter().find(|f| &f.name == name);
+        // the profile of the `<remove>` block, which is the one the registry was filtered with
+        let profile = feature.and_then(|f| {
+            f.removes.iter()
+                     .find(|r| r.enums.iter().chain(r.commands.iter()).any(|n| is_prefixed(n, ident, ns)))
+                     .and_then(|r| r.profile.clone())
+        });
+        Reason::RemovedIn {
+            feature: name.clone(),
+            version: feature.map(|f| f.number),
+            profile: profile,
+        }
+    }).collect();
+    if reasons.is_empty() {
+        reasons.push(Reason::NotRequired);
+    }
+    Change { ident: ident.to_string(), kind: ChangeKind::Removed, reasons: reasons }
+}
+
+/// Returns true if `name`, as written in a `<require>` or `<remove>` block, is `ident` with the
+///  enum or command prefix of the API, like `GL_` or `glX`.
+fn is_prefixed(name: &str, ident: &str, ns: Ns) -> bool {
+    name.ends_with(ident) && {
+        let prefix = &name[.. name.len() - ident.len()];
+        prefix == ns.info().enum_prefix || prefix == ns.info().cmd_prefix
+    }
+}
+
+fn cmd_changes(old: &Cmd, new: &Cmd) -> Vec<Reason> {
+    let mut reasons = Vec::new();
+    if old.proto.ty != new.proto.ty {
+        reasons.push(Reason::ReturnType { old: old.proto.ty.clone(), new: new.proto.ty.clone() });
+    }
+    if old.params.len() != new.params.len() ||
+       old.params.iter().zip(new.params.iter()).any(|(a, b)| a.ty != b.ty)
+    {
+        reasons.push(Reason::Params { old: fmt_params(&old.params), new: fmt_params(&new.params) });
+    }
+    reasons
+}
+
+fn fmt_params(params: &[Binding]) -> String {
+    params.iter().map(|p| format!("{} {}", p.ty, p.ident)).collect::<Vec<_>>().join(", ")
+}
+
+fn enum_changes(old: &Enum, new: &Enum) -> Vec<Reason> {
+    let mut reasons = Vec::new();
+    if old.value != new.value {
+        reasons.push(Reason::Value { old: old.value.clone(), new: new.value.clone() });
+    }
+    if old.ty != new.ty {
+        reasons.push(Reason::Type { old: old.ty.clone(), new: new.ty.clone() });
+    }
+    reasons
+}
+
+/// The differences between two registries. See `diff`.
+pub struct Diff {
+    /// The commands that changed, sorted by identifier.
+    pub cmds: Vec<Change>,
+    /// The enums that changed, sorted by identifier.
+    pub enums: Vec<Change>,
+}
+
+/// A command or an enum that is only in one of the registries, or that differs between them.
+pub struct Change {
+    /// The identifier of the command or enum, without prefix.
+    pub ident: String,
+    pub kind: ChangeKind,
+    /// Why the command or enum was added, removed or changed. May be empty for additions to an
+    ///  unfiltered registry.
+    pub reasons: Vec<Reason>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Reason {
+    /// The command or enum is required by these features or extensions of the new registry.
+    IntroducedIn(Vec<String>),
+    /// A feature of the new registry removes the command or enum.
+    RemovedIn {
+        feature: String,
+        version: Option<Version>,
+        /// The profile of the `<remove>` block, like `core`.
+        profile: Option<String>,
+    },
+    /// No feature or extension of the new registry requires the command or enum, for example
+    ///  because the new registry is for another API.
+    NotRequired,
+    /// The return type of a command changed.
+    ReturnType {
+        old: String,
+        new: String,
+    },
+    /// The types of the parameters of a command changed. Contains the parameter lists.
+    Params {
+        old: String,
+        new: String,
+    },
+    /// The value of an enum changed.
+    Value {
+        old: String,
+        new: String,
+    },
+    /// The `type` attribute of an enum changed.
+    Type {
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Reason::IntroducedIn(ref names) => write!(fmt, "introduced in {}", names.join(", ")),
+            Reason::RemovedIn { version: Some(version), profile: Some(ref profile), .. } =>
+                write!(fmt, "removed in {} {}", version, profile),
+            Reason::RemovedIn { version: Some(version), profile: None, .. } =>
+                write!(fmt, "removed in {}", version),
+            Reason::RemovedIn { ref feature, version: None, .. } =>
+                write!(fmt, "removed in {}", feature),
+            Reason::NotRequired => write!(fmt, "not required by the new registry"),
+            Reason::ReturnType { ref old, ref new } =>
+                write!(fmt, "return type changed from `{}` to `{}`", old, new),
+            Reason::Params { ref old, ref new } =>
+                write!(fmt, "parameters changed from `({})` to `({})`", old, new),
+            Reason::Value { ref old, ref new } =>
+                write!(fmt, "value changed from `{}` to `{}`", old, new),
+            Reason::Type { ref old, ref new } =>
+                write!(fmt, "type changed from `{}` to `{}`",
+                       old.as_ref().map_or("", |t| &t[..]), new.as_ref().map_or("", |t| &t[..])),
+        }
+    }
+}
+
+impl Change {
+    /// Returns the reasons of the change, separated by `; `.
+    pub fn fmt_reasons(&self) -> String {
+        self.reasons.iter().map(|r| r.to_string()).collect::<Vec<_>>().join("; ")
+    }
+}
+
+impl Diff {
+    /// Returns true if the two registries define the same commands and enums.
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty() && self.enums.is_empty()
+    }
+
+    /// Writes the report as plain text, with one line per change, like
+    ///  `- Begin (removed in 3.2 core)`.
+    pub fn write_text<W>(&self, dest: &mut W) -> io::Result<()> where W: io::Write {
+        for &(title, changes) in [("Commands", &self.cmds), ("Enums", &self.enums)].iter() {
+            try!(writeln!(dest, "{}: {} added, {} removed, {} changed", title,
+                          count(changes, ChangeKind::Added), count(changes, ChangeKind::Removed),
+                          count(changes, ChangeKind::Changed)));
+            for change in changes.iter() {
+                let sign = match change.kind {
+                    ChangeKind::Added => '+',
+                    ChangeKind::Removed => '-',
+                    ChangeKind::Changed => '~',
+                };
+                if change.reasons.is_empty() {
+                    try!(writeln!(dest, "  {} {}", sign, change.ident));
+                } else {
+                    try!(writeln!(dest, "  {} {} ({})", sign, change.ident, change.fmt_reasons()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the report as Markdown, with a section per kind of change.
+    pub fn write_markdown<W>(&self, dest: &mut W) -> io::Result<()> where W: io::Write {
+        for &(title, changes) in [("commands", &self.cmds), ("enums", &self.enums)].iter() {
+            for &(kind, heading) in [(ChangeKind::Added, "Added"),
+                                     (ChangeKind::Removed, "Removed"),
+                                     (ChangeKind::Changed, "Changed")].iter() {
+                if count(changes, kind) == 0 {
+                    continue;
+                }
+                try!(writeln!(dest, "## {} {}\n", heading, title));
+                for change in changes.iter().filter(|c| c.kind == kind) {
+                    if change.reasons.is_empty() {
+                        try!(writeln!(dest, "- `{}`", change.ident));
+                    } else {
+                        try!(writeln!(dest, "- `{}`: {}", change.ident, change.fmt_reasons()));
+                    }
+                }
+                try!(writeln!(dest, ""));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn count(changes: &[Change], kind: ChangeKind) -> usize {
+    changes.iter().filter(|c| c.kind == kind).count()
+}
@@ -0,0 +1,210 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use registry::Version;
+
+/// An error that occurred while parsing a registry or generating bindings from it.
+#[derive(Debug)]
+pub enum Error {
+    /// The registry is not well-formed XML.
+    Xml {
+        row: u64,
+        col: u64,
+        msg: String,
+    },
+    /// The parser found something it did not expect at the given element path, for example
+    ///  `registry/commands/command`.
+    UnexpectedElement {
+        path: String,
+        found: String,
+    },
+    /// An element is missing an attribute that the parser requires.
+    MissingAttribute {
+        path: String,
+        attribute: String,
+    },
+    /// A version number is not of the form `major.minor`.
+    InvalidVersion(String),
+    /// A profile is neither `core` nor `compatibility`.
+    InvalidProfile(String),
+    /// The requested API has profiles, but none was given.
+    ProfileRequired(String),
+    /// The registry does not define any version of the requested API.
+    UnknownApi(String),
+    /// The requested version of the API is not defined by the registry.
+    UnknownVersion {
+        api: String,
+        version: Version,
+        /// The versions that the registry does define for this API.
+        available: Vec<Version>,
+    },
+    /// The requested extension is not defined by the registry.
+    UnknownExtension(String),
+    /// The requested extension exists, but does not support the requested API.
+    UnsupportedExtension {
+        name: String,
+        api: String,
+    },
+    /// The registry defines an enum several times with different values, and none of the
+    ///  definitions is specific to the requested API. Also returned when two merged registries
+    ///  define an enum with different values.
+    ConflictingEnum {
+        name: String,
+        values: Vec<String>,
+    },
+    /// An enum is an alias of another enum, but their values differ.
+    ConflictingEnumAlias {
+        name: String,
+        alias: String,
+        values: Vec<String>,
+    },
+    /// Following the `alias` attributes from an enum leads back to it.
+    EnumAliasCycle(String),
+    /// Two merged registries define a command with different signatures.
+    ConflictingCommand {
+        name: String,
+        signatures: Vec<String>,
+    },
+    /// Two merged registries define a type differently.
+    ConflictingType {
+        name: String,
+    },
+    /// A C type used by a command could not be converted to a Rust type.
+    UnknownType {
+        ty: String,
+        cmd: String,
+        /// The parameter using the type, or `None` for the return type.
+        param: Option<String>,
+    },
+    /// A registry read with `Registry::from_json` is not valid JSON, or does not follow the schema
+    ///  written by `Registry::write_json`.
+    Json {
+        /// The location of the offending value, like `cmds[12].params[0]`, or an empty string for
+        ///  errors about the document as a whole.
+        path: String,
+        msg: String,
+    },
+    /// No registry file was given for an API that `khronos_api` doesn't define, like an
+    ///  `Ns::Custom` API.
+    MissingSource(String),
+    /// The registry file could not be read.
+    ReadRegistry {
+        path: PathBuf,
+        err: io::Error,
+    },
+    /// Writing the generated bindings failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Xml { row, col, ref msg } =>
+                write!(fmt, "XML error at {}:{}: {}", row + 1, col + 1, msg),
+            Error::UnexpectedElement { ref path, ref found } =>
+                write!(fmt, "unexpected {} in `{}`", found, path),
+            Error::MissingAttribute { ref path, ref attribute } =>
+                write!(fmt, "`{}` is missing the `{}` attribute", path, attribute),
+            Error::InvalidVersion(ref version) =>
+                write!(fmt, "`{}` is not a version of the form `major.minor`", version),
+            Error::InvalidProfile(ref profile) =>
+                write!(fmt, "`{}` is not a profile, expected `core` or `compatibility`", profile),
+            Error::ProfileRequired(ref api) =>
+                write!(fmt, "`{}` has profiles, but no profile was requested", api),
+            Error::UnknownApi(ref api) =>
+                write!(fmt, "the registry does not define any version of `{}`", api),
+            Error::UnknownVersion { ref api, ref version, ref available } =>
+                write!(fmt, "version {} of `{}` is not in the registry (available versions: {})",
+                       version, api,
+                       available.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")),
+            Error::UnknownExtension(ref name) =>
+                write!(fmt, "extension `{}` is not in the registry", name),
+            Error::UnsupportedExtension { ref name, ref api } =>
+                write!(fmt, "extension `{}` does not support the `{}` API", name, api),
+            Error::ConflictingEnum { ref name, ref values } =>
+                write!(fmt, "enum `{}` has conflicting definitions: {}", name, values.join(", ")),
+            Error::ConflictingEnumAlias { ref name, ref alias, ref values } =>
+                write!(fmt, "enum `{}` is an alias of `{}`, but their values differ: {}", name, alias,
+                       values.join(", ")),
+            Error::EnumAliasCycle(ref name) =>
+                write!(fmt, "the aliases of enum `{}` form a cycle", name),
+            Error::ConflictingCommand { ref name, ref signatures } =>
+                write!(fmt, "command `{}` has conflicting signatures: {}", name,
+                       signatures.join(", ")),
+            Error::ConflictingType { ref name } =>
+                write!(fmt, "type `{}` has conflicting definitions", name),
+            Error::UnknownType { ref ty, ref cmd, param: Some(ref param) } =>
+                write!(fmt, "cannot convert type `{}` of parameter `{}` of `{}` to a Rust type",
+                       ty, param, cmd),
+            Error::UnknownType { ref ty, ref cmd, param: None } =>
+                write!(fmt, "cannot convert return type `{}` of `{}` to a Rust type", ty, cmd),
+            Error::Json { ref path, ref msg } if path.is_empty() =>
+                write!(fmt, "invalid registry JSON: {}", msg),
+            Error::Json { ref path, ref msg } =>
+                write!(fmt, "invalid registry JSON at `{}`: {}", path, msg),
+            Error::MissingSource(ref api) =>
+                write!(fmt, "no registry file was given for the `{}` API", api),
+            Error::ReadRegistry { ref path, ref err } =>
+                write!(fmt, "cannot read `{}`: {}", path.display(), err),
+            Error::Io(ref err) => write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Xml { .. } => "malformed registry XML",
+            Error::UnexpectedElement { .. } => "unexpected element in the registry",
+            Error::MissingAttribute { .. } => "missing attribute in the registry",
+            Error::InvalidVersion(_) => "invalid version number",
+            Error::InvalidProfile(_) => "invalid profile",
+            Error::ProfileRequired(_) => "missing profile",
+            Error::UnknownApi(_) => "unknown API",
+            Error::UnknownVersion { .. } => "unknown API version",
+            Error::UnknownExtension(_) => "unknown extension",
+            Error::UnsupportedExtension { .. } => "extension does not support the API",
+            Error::ConflictingEnum { .. } => "conflicting enum definitions",
+            Error::ConflictingEnumAlias { .. } => "conflicting enum alias",
+            Error::EnumAliasCycle(_) => "cyclic enum aliases",
+            Error::ConflictingCommand { .. } => "conflicting command definitions",
+            Error::ConflictingType { .. } => "conflicting type definitions",
+            Error::UnknownType { .. } => "unknown C type",
+            Error::Json { .. } => "invalid registry JSON",
+            Error::MissingSource(_) => "missing registry file",
+            Error::ReadRegistry { .. } => "cannot read the registry",
+            Error::Io(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::ReadRegistry { ref err, .. } => Some(err),
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
@@ -15,6 +15,7 @@
 
 use registry::{Registry, Ns};
 use std::io;
+use super::Options;
 use std::collections::HashMap;
 
 #[allow(missing_copy_implementations)]
@@ -22,9 +23,15 @@ pub struct DebugStructGenerator;
 
 impl super::Generator for DebugStructGenerator {
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
         try!(write_header(dest));
-        try!(write_type_aliases(&ns, dest));
-        try!(write_enums(registry, dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
         try!(write_fnptr_struct_def(dest));
         try!(write_panicking_fns(&ns, dest));
 
@@ -37,7 +44,8 @@ impl super::Generator for DebugStructGenerator {
         fn_overrides.insert("glGetError", ("fallback_get_error", "debug_output_fallback_required"));
 
         try!(write_struct(registry, &ns, &fn_overrides, dest));
-        try!(write_impl(registry, &ns, &fn_overrides, dest));
+        try!(write_impl(registry, &ns, &fn_overrides, options, dest));
+        try!(super::gen_vec_helpers(registry, options, Some(ns.fmt_struct_name()), dest));
         Ok(())
     }
 }
@@ -62,7 +70,7 @@ fn write_header<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_type_aliases`.
-fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, r#"
         pub mod types {{
             #![allow(non_camel_case_types)]
@@ -71,18 +79,21 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
             #![allow(missing_copy_implementations)]
     "#));
 
-    try!(super::gen_type_aliases(ns, dest));
+    // the types used by the debug output code
+    try!(super::gen_type_aliases(registry, &["GLenum", "GLuint", "GLsizei", "GLchar", "GLboolean", "GLDEBUGPROC"], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for e in registry.enum_iter() {
-        try!(super::gen_enum_item(e, "types::", dest));
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
     }
 
-    Ok(())
+    super::gen_equivalent_enums(registry, options, "types::", dest)
 }
 
 /// Creates a `FnPtr` structure which contains the store for a single binding.
@@ -177,8 +188,9 @@ fn write_struct<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&
     for c in registry.cmd_iter() {
         let symbol = super::gen_symbol_name(ns, &c.proto.ident);
 
-        if let Some(v) = registry.aliases.get(&c.proto.ident) {
-            try!(writeln!(dest, "/// Fallbacks: {}", v.join(", ")));
+        let fallbacks = registry.fallbacks(&c.proto.ident);
+        if !fallbacks.is_empty() {
+            try!(writeln!(dest, "/// Fallbacks: {}", fallbacks.join(", ")));
         }
         if fn_overrides.contains_key(&*symbol) {
             try!(writeln!(dest,
@@ -197,7 +209,7 @@ fn write_struct<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&str, &str)>, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&str, &str)>, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest,
         "impl {ns} {{",
         ns = ns.fmt_struct_name()
@@ -248,19 +260,14 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&st
         let load = format!(
             "metaloadfn(\"{symbol}\", &[{fallbacks}])",
             symbol = symbol,
-            fallbacks = match registry.aliases.get(&c.proto.ident) {
-                Some(fbs) => {
-                    fbs.iter()
-                       .map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name)))
-                       .collect::<Vec<_>>().join(", ")
-                },
-                None => format!(""),
-            }
+            fallbacks = registry.fallbacks(&c.proto.ident).iter()
+                .map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name)))
+                .collect::<Vec<_>>().join(", ")
         );
 
         match fn_overrides.get(&*symbol) {
             Some(&(fn_override, condition)) => {
-                let typed_params = super::gen_parameters(c, false, true);
+                let typed_params = super::gen_parameters(registry, options, c, false, true);
                 let return_suffix = super::gen_return_type(c);
                 let override_params = typed_params_to_override_params(ns.fmt_struct_name(), typed_params, &return_suffix);
 
@@ -304,8 +311,8 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&st
 
     for c in registry.cmd_iter() {
         let symbol = super::gen_symbol_name(ns, &c.proto.ident);
-        let idents = super::gen_parameters(c, true, false);
-        let typed_params = super::gen_parameters(c, false, true);
+        let idents = super::gen_parameters(registry, options, c, true, false);
+        let typed_params = super::gen_parameters(registry, options, c, false, true);
         let return_suffix = super::gen_return_type(c);
         let println = format!("(self.trace_callback)(\"{ident}\", &format!(\"{params}\"{args}), &format!(\"{{:?}}\", r));",
                                 ident = c.proto.ident,
@@ -347,6 +354,7 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&st
             }
         };
 
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
         try!(writeln!(dest,
             "#[allow(non_snake_case)] #[allow(unused_variables)] #[allow(dead_code)]
             #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
@@ -357,7 +365,7 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, fn_overrides: &HashMap<&str, (&st
             }}",
             name = c.proto.ident,
             full_name = symbol,
-            params = super::gen_parameters(c, true, true).join(", "),
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
             return_suffix = super::gen_return_type(c),
             call = call,
             println = println
@@ -15,17 +15,25 @@
 
 use registry::{Registry, Ns};
 use std::io;
+use super::Options;
 
 #[allow(missing_copy_implementations)]
 pub struct GlobalGenerator;
 
 impl super::Generator for GlobalGenerator {
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
         try!(write_header(dest));
         try!(write_metaloadfn(dest));
-        try!(write_type_aliases(&ns, dest));
-        try!(write_enums(registry, dest));
-        try!(write_fns(registry, dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
+        try!(write_fns(registry, options, dest));
+        try!(super::gen_vec_helpers(registry, options, None, dest));
         try!(write_fnptr_struct_def(dest));
         try!(write_ptrs(registry, dest));
         try!(write_fn_mods(registry, &ns, dest));
@@ -69,7 +77,7 @@ fn write_metaloadfn<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_type_aliases`.
-fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, r#"
         pub mod types {{
             #![allow(non_camel_case_types)]
@@ -78,7 +86,8 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
             #![allow(missing_copy_implementations)]
     "#));
 
-    try!(super::gen_type_aliases(ns, dest));
+    try!(super::gen_type_aliases(registry, &[], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
 
     writeln!(dest, "
         }}
@@ -86,23 +95,26 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for e in registry.enum_iter() {
-        try!(super::gen_enum_item(e, "types::", dest));
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
     }
 
-    Ok(())
+    super::gen_equivalent_enums(registry, options, "types::", dest)
 }
 
 /// Creates the functions corresponding to the GL commands.
 ///
 /// The function calls the corresponding function pointer stored in the `storage` module created
 ///  by `write_ptrs`.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_fns<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for c in registry.cmd_iter() {
-        if let Some(v) = registry.aliases.get(&c.proto.ident) {
-            try!(writeln!(dest, "/// Fallbacks: {}", v.join(", ")));
+        let fallbacks = registry.fallbacks(&c.proto.ident);
+        if !fallbacks.is_empty() {
+            try!(writeln!(dest, "/// Fallbacks: {}", fallbacks.join(", ")));
         }
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
 
         try!(writeln!(dest,
             "#[allow(non_snake_case, unused_variables, dead_code)] #[inline]
@@ -111,10 +123,10 @@ fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io
                     (storage::{name}.f)({idents}) \
             }}",
             name = c.proto.ident,
-            params = super::gen_parameters(c, true, true).join(", "),
-            typed_params = super::gen_parameters(c, false, true).join(", "),
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
+            typed_params = super::gen_parameters(registry, options, c, false, true).join(", "),
             return_suffix = super::gen_return_type(c),
-            idents = super::gen_parameters(c, true, false).join(", "),
+            idents = super::gen_parameters(registry, options, c, true, false).join(", "),
         ));
     }
 
@@ -173,12 +185,10 @@ fn write_ptrs<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: i
 ///  created by `write_ptrs`.
 fn write_fn_mods<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
     for c in registry.cmd_iter() {
-        let fallbacks = match registry.aliases.get(&c.proto.ident) {
-            Some(v) => {
-                let names = v.iter().map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name[..]))).collect::<Vec<_>>();
-                format!("&[{}]", names.join(", "))
-            }, None => "&[]".to_string(),
-        };
+        let fallbacks = registry.fallbacks(&c.proto.ident).iter()
+            .map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name[..])))
+            .collect::<Vec<_>>();
+        let fallbacks = format!("&[{}]", fallbacks.join(", "));
         let fnname = &c.proto.ident[..];
         let symbol = super::gen_symbol_name(ns, &c.proto.ident[..]);
         let symbol = &symbol[..];
@@ -0,0 +1,28 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::{Registry, Ns};
+use std::io;
+
+/// Writes the filtered registry as JSON instead of Rust code, for tools that want to inspect the
+///  bindings or cache the registry. The result can be loaded back with `Registry::from_json`.
+#[allow(missing_copy_implementations)]
+pub struct JsonGenerator;
+
+impl super::Generator for JsonGenerator {
+    fn write<W>(&self, registry: &Registry, _: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        registry.write_json(dest)
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::{Registry, Ns};
+use std::io;
+use super::Options;
+
+/// Writes bindings with the same API as `StructGenerator`, whose commands don't call OpenGL but
+///  record each call in a log, as a `Call` with the arguments of the command.
+///
+/// What a command returns or writes to its pointers can be scripted with its `MockFn` field,
+///  which queues return values and sets a closure that is called instead of the command. Without
+///  them, the commands return zero, or panic if zero isn't a valid value of the return type, like
+///  the function pointers returned by `eglGetProcAddress`.
+///
+/// Unlike the struct of `StructGenerator`, the mock isn't `Clone`, as its closures are boxed.
+#[allow(missing_copy_implementations)]
+pub struct MockGenerator;
+
+impl super::Generator for MockGenerator {
+    fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
+        try!(write_header(dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
+        try!(write_call_enum(registry, options, dest));
+        try!(write_mock_fn_def(dest));
+        try!(write_struct(registry, &ns, options, dest));
+        try!(write_impl(registry, &ns, options, dest));
+        try!(super::gen_vec_helpers(registry, options, Some(ns.fmt_struct_name()), dest));
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, r#"
+        mod __gl_imports {{
+            extern crate gl_common;
+            extern crate libc;
+            pub use std::mem;
+            pub use std::cell::RefCell;
+            pub use std::collections::VecDeque;
+        }}
+    "#)
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_type_aliases`.
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
+    try!(writeln!(dest, r#"
+        pub mod types {{
+            #![allow(non_camel_case_types)]
+            #![allow(non_snake_case)]
+            #![allow(dead_code)]
+            #![allow(missing_copy_implementations)]
+    "#));
+
+    try!(super::gen_type_aliases(registry, &[], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
+
+    writeln!(dest, "}}")
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
+    for e in registry.enum_iter() {
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
+    }
+
+    super::gen_equivalent_enums(registry, options, "types::", dest)
+}
+
+/// Creates a `Call` enum with a variant for each command, whose fields are the arguments of the
+///  command.
+fn write_call_enum<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
+    try!(writeln!(dest, "
+        /// A call to a command of the mock, with its arguments. Pointers are recorded as-is, not
+        ///  the data they point to.
+        #[allow(non_camel_case_types)]
+        #[allow(non_snake_case)]
+        #[allow(dead_code)]
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum Call {{"));
+
+    for c in registry.cmd_iter() {
+        if c.params.is_empty() {
+            try!(writeln!(dest, "{},", c.proto.ident));
+        } else {
+            try!(writeln!(dest, "{} {{ {} }},", c.proto.ident,
+                          super::gen_parameters(registry, options, c, true, true).join(", ")));
+        }
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates a `MockFn` structure which contains the scripted behavior of a single command.
+fn write_mock_fn_def<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, "
+        /// The scripted behavior of a command of the mock. `F` is the type of the closure that
+        ///  replaces the command, and `R` the type that the command returns.
+        #[allow(dead_code)]
+        pub struct MockFn<F: ?Sized, R> {{
+            /// The values that the next calls return, before the handler is tried.
+            returns: __gl_imports::RefCell<__gl_imports::VecDeque<R>>,
+            /// The closure that is called with the arguments of the command.
+            handler: __gl_imports::RefCell<Option<Box<F>>>,
+        }}
+
+        #[allow(dead_code)]
+        impl<F: ?Sized, R> MockFn<F, R> {{
+            fn new() -> MockFn<F, R> {{
+                MockFn {{
+                    returns: __gl_imports::RefCell::new(__gl_imports::VecDeque::new()),
+                    handler: __gl_imports::RefCell::new(None),
+                }}
+            }}
+
+            /// Queues a value for a call to return. The queued values are returned in order,
+            ///  without calling the handler.
+            pub fn push_return(&self, value: R) {{
+                self.returns.borrow_mut().push_back(value);
+            }}
+
+            /// Sets the closure that is called with the arguments of the command when no value
+            ///  is queued, for example to write to the pointers that the command takes.
+            ///
+            /// The closure must not call the same command of the mock.
+            pub fn set_handler(&self, handler: Box<F>) {{
+                *self.handler.borrow_mut() = Some(handler);
+            }}
+
+            /// Removes the closure set with `set_handler`, and the queued values.
+            pub fn reset(&self) {{
+                self.returns.borrow_mut().clear();
+                *self.handler.borrow_mut() = None;
+            }}
+
+            /// Always returns `true`: the commands of the mock don't need to be loaded.
+            #[inline]
+            pub fn is_loaded(&self) -> bool {{
+                true
+            }}
+        }}
+    ")
+}
+
+/// Creates a structure which stores the log of the calls and the `MockFn` of each command.
+///
+/// The name of the struct corresponds to the namespace.
+fn write_struct<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()>
+                   where W: io::Write
+{
+    try!(writeln!(dest, "
+        #[allow(non_camel_case_types)]
+        #[allow(non_snake_case)]
+        #[allow(dead_code)]
+        pub struct {ns} {{
+            calls: __gl_imports::RefCell<Vec<Call>>,",
+        ns = ns.fmt_struct_name()
+    ));
+
+    for c in registry.cmd_iter() {
+        try!(writeln!(dest,
+            "pub {name}: MockFn<FnMut({typed_params}) -> {return_suffix}, {return_suffix}>,",
+            name = c.proto.ident,
+            typed_params = super::gen_parameters(registry, options, c, false, true).join(", "),
+            return_suffix = super::gen_return_type(c),
+        ));
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `impl` of the structure created by `write_struct`.
+fn write_impl<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
+    try!(writeln!(dest,
+        "impl {ns} {{
+            /// Creates a mock with an empty log, whose commands return zero, or panic for the
+            ///  return types where zero isn't valid.
+            #[allow(dead_code)]
+            pub fn new() -> {ns} {{
+                {ns} {{
+                    calls: __gl_imports::RefCell::new(Vec::new()),",
+        ns = ns.fmt_struct_name()
+    ));
+
+    for c in registry.cmd_iter() {
+        try!(writeln!(dest, "{name}: MockFn::new(),", name = c.proto.ident));
+    }
+
+    try!(writeln!(dest,
+            "}}
+        }}
+
+        /// Creates a mock without calling `loadfn`, like `new`.
+        #[allow(dead_code)]
+        #[allow(unused_variables)]
+        pub fn load_with<F>(loadfn: F) -> {ns} where F: FnMut(&str) -> *const __gl_imports::libc::c_void {{
+            {ns}::new()
+        }}
+
+        /// Creates a mock without using `loader`, like `new`.
+        #[allow(dead_code)]
+        #[allow(unused_variables)]
+        pub fn load<T: __gl_imports::gl_common::GlFunctionsSource>(loader: &T) -> {ns} {{
+            {ns}::new()
+        }}
+
+        /// Returns the calls made so far, in order.
+        #[allow(dead_code)]
+        pub fn calls(&self) -> Vec<Call> {{
+            self.calls.borrow().clone()
+        }}
+
+        /// Returns the calls made so far, in order, and empties the log.
+        #[allow(dead_code)]
+        pub fn take_calls(&self) -> Vec<Call> {{
+            __gl_imports::mem::replace(&mut *self.calls.borrow_mut(), Vec::new())
+        }}",
+        ns = ns.fmt_struct_name()
+    ));
+
+    for c in registry.cmd_iter() {
+        let idents = super::gen_parameters(registry, options, c, true, false);
+        let call = if c.params.is_empty() {
+            format!("Call::{}", c.proto.ident)
+        } else {
+            format!("Call::{} {{ {} }}", c.proto.ident,
+                    idents.iter().map(|i| format!("{i}: {i}", i = i)).collect::<Vec<_>>().join(", "))
+        };
+
+        // zero isn't a valid function pointer, so those commands need a scripted value
+        let fallback = if super::ty::is_zeroable(registry, &c.proto.ty) {
+            "__gl_imports::mem::zeroed()".to_string()
+        } else {
+            format!("panic!(\"no return value scripted for {}\")", c.proto.ident)
+        };
+
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
+        try!(writeln!(dest,
+            "#[allow(non_snake_case)] #[allow(unused_variables)] #[allow(dead_code)]
+            #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+                self.calls.borrow_mut().push({call}); \
+                if let Some(value) = self.{name}.returns.borrow_mut().pop_front() {{ return value; }} \
+                match *self.{name}.handler.borrow_mut() {{ \
+                    Some(ref mut handler) => handler({idents}), \
+                    None => {fallback}, \
+                }} \
+            }}",
+            name = c.proto.ident,
+            fallback = fallback,
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
+            return_suffix = super::gen_return_type(c),
+            call = call,
+            idents = idents.join(", "),
+        ))
+    }
+
+    writeln!(dest, "}}")
+}
@@ -1,9 +1,14 @@
-use registry::{Enum, Registry, Cmd, Ns};
+use registry::{Binding, Enum, Registry, Cmd, Len, Ns};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
+use Error;
 
 mod ty;
 pub mod debug_struct_gen;
 pub mod global_gen;
+pub mod json_gen;
+pub mod mock_gen;
+pub mod safe_wrapper_gen;
 pub mod static_gen;
 pub mod struct_gen;
 pub mod static_struct_gen;
@@ -12,55 +17,78 @@ pub mod static_struct_gen;
 pub trait Generator {
     /// Builds the GL bindings.
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write;
+
+    /// Builds the GL bindings with the given options. Generators that don't have options ignore
+    ///  them.
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, _options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
+        self.write(registry, ns, dest)
+    }
 }
 
-/// This function generates a `const name: type = value;` item.
-pub fn gen_enum_item<W>(enm: &Enum, types_prefix: &str, dest: &mut W) -> io::Result<()> where W: io::Write {
-    // computing the name of the enum
-    // if the original starts with a digit, adding an underscore prefix.
-    let ident = if (enm.ident.chars().next().unwrap()).is_numeric() {
-        format!("_{}", enm.ident)
-    } else {
-        enm.ident.clone()
-    };
+/// Options that change what the generators write.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Marks the commands and enums that a later version removes from the core profile, like
+    ///  `glBegin` in GL 3.0 or compatibility bindings, with `#[deprecated]`.
+    pub deprecations: bool,
+    /// Also generates the enums that are aliases of the generated ones, or the other way around,
+    ///  like `CLIP_PLANE0` next to `CLIP_DISTANCE0`. See `Registry::equivalent_enums`.
+    pub enum_aliases: bool,
+    /// Also generates an `arrays` module with a helper for each vector command, like
+    ///  `arrays::Color3fv(&[GLfloat; 3])`, that passes the array to the vector command. See
+    ///  `gen_vec_helpers`.
+    pub vec_helpers: bool,
+    /// Generates a newtype for each `<group>` of enums that a `GLenum` or `GLbitfield` parameter
+    ///  names, like `types::PrimitiveType` or the flags `types::ClearBufferMask`, and makes the
+    ///  parameters take it. See `gen_enum_groups`.
+    pub enum_groups: bool,
+}
 
-    // if the enum has the value of the form `((Type)Value)`, then `val_regexed` contains `(Type, Value)`
-    let val_regexed = {
-        if enm.value.starts_with("((") && enm.value.ends_with(")") {
-            let separator = enm.value.chars().skip(2).position(|c| c == ')').unwrap();
-            Some((&enm.value[2 .. separator + 2], enm.value[separator + 3 ..].trim_matches(')')))
-        } else {
-            None
-        }
-    };
+/// A generator that writes its bindings with the given options.
+///
+/// ~~~ignore
+/// let options = Options { deprecations: true, .. Options::default() };
+/// gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, options), ...)
+/// ~~~
+pub struct WithOptions<G>(pub G, pub Options);
 
-    // computing the type of the enum
-    let ty = {
-        // some enums have a value of the form `((Type)Value)` ; if this is the case, we need to
-        //  replace the type of the enum (which is GLenum by default) by the type in the expression
-        if let Some((ty, _)) = val_regexed {
-            // if the value is like `((Type)Value)`, then the type is `types::Type`
-            format!("{}{}", types_prefix, ty)
-
-        } else if enm.value.starts_with("\"") {
-            // some values are of the form "Value" ; if this is the case, we use `&'static str`
-            //  instead of `GLenum`
-            "&'static str".to_string()
+impl<G> Generator for WithOptions<G> where G: Generator {
+    fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.0.write_with_options(registry, ns, &self.1, dest)
+    }
+}
 
-        } else {
-            // some values are `TRUE` or `FALSE`, in which case we use `GLboolean` instead of
-            //  `GLenum`
-            match &ident[..] {
-                "TRUE" | "FALSE" => format!("{}GLboolean", types_prefix),
-                _ => match enm.ty {
-                    Some(ref s) if &s[..] == "ull" => format!("{}GLuint64", types_prefix),
-                    _ => format!("{}GLenum", types_prefix)
-                }
-            }
+/// Writes a `#[deprecated]` attribute if `options` ask for it and a later version removes the
+///  command or enum `ident` from the core profile.
+pub fn gen_deprecated<W>(registry: &Registry, options: &Options, ident: &str, dest: &mut W)
+                         -> io::Result<()> where W: io::Write
+{
+    match registry.removed_from_core(ident) {
+        Some(version) if options.deprecations => {
+            writeln!(dest, "#[deprecated(note = \"removed from core profile in GL {}\")]", version)
         }
+        _ => Ok(()),
+    }
+}
+
+/// This function generates a `const name: type = value;` item.
+pub fn gen_enum_item<W>(registry: &Registry, enm: &Enum, types_prefix: &str, dest: &mut W) -> io::Result<()>
+                        where W: io::Write
+{
+    let ident = gen_enum_ident(enm);
+
+    // computing the type of the enum
+    let ty = match gen_enum_ty(registry, enm) {
+        Some(ty) => format!("{}{}", types_prefix, ty),
+        // some values are of the form "Value" ; if this is the case, we use `&'static str`
+        //  instead of `GLenum`
+        None => "&'static str".to_string(),
     };
 
     // computing the value of the enum
+    let val_regexed = split_enum_cast(&enm.value);
     let value = {
         // similar to the type, some values are `((Type)Value)`
         // replacing "((Type)Value)" by "Value as types::Type"
@@ -78,39 +106,324 @@ pub fn gen_enum_item<W>(enm: &Enum, types_prefix: &str, dest: &mut W) -> io::Res
     ", ident, ty, value)
 }
 
-/// Generates all the type aliases for a namespace.
+/// Returns the name of the constant of an enum: its identifier, with an underscore prefix if it
+///  starts with a digit.
+fn gen_enum_ident(enm: &Enum) -> String {
+    if (enm.ident.chars().next().unwrap()).is_numeric() {
+        format!("_{}", enm.ident)
+    } else {
+        enm.ident.clone()
+    }
+}
+
+/// Writes the newtypes of the enum groups, if `options` ask for them. They belong in the `types`
+///  module.
 ///
-/// Aliases are either `pub type = ...` or `#[repr(C)] pub struct ... { ... }` and contain all the
-///  things that we can't obtain from the XML files.
-pub fn gen_type_aliases<W>(namespace: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
-    match *namespace {
-        Ns::Gl | Ns::Gles1 | Ns::Gles2 => {
-            try!(ty::build_gl_aliases(dest));
+/// Each group that a `GLenum` or `GLbitfield` parameter names becomes a `#[repr(transparent)]`
+///  newtype, with the enums of the group that are in the registry as associated constants:
+///
+/// ~~~ignore
+/// pub struct PrimitiveType(pub GLenum);
+///
+/// impl PrimitiveType {
+///     pub const TRIANGLES: PrimitiveType = PrimitiveType(0x0004);
+/// }
+/// ~~~
+///
+/// The groups of bit flags (see `Registry::is_bitmask_group`) can also be combined with `|` and
+///  `&`, tested with `contains`, and are formatted with `Debug` as the names of their flags,
+///  like `ClearBufferMask(COLOR_BUFFER_BIT | DEPTH_BUFFER_BIT)`.
+pub fn gen_enum_groups<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()>
+                          where W: io::Write
+{
+    if !options.enum_groups {
+        return Ok(());
+    }
+
+    // the groups named by parameters, and whether one of them is a `GLbitfield`
+    let mut used: HashMap<&str, bool> = HashMap::new();
+    for c in registry.cmd_iter() {
+        for p in c.params.iter() {
+            if let Some(group) = param_group(registry, options, p) {
+                *used.entry(group).or_insert(false) |= p.ty.trim() == "GLbitfield";
+            }
         }
+    }
 
-        Ns::Glx => {
-            try!(ty::build_gl_aliases(dest));
-            try!(ty::build_x_aliases(dest));
-            try!(ty::build_glx_aliases(dest));
+    for group in registry.groups.iter() {
+        // a merged registry can define a group twice
+        let is_bitfield = match used.remove(&group.name[..]) {
+            Some(is_bitfield) => is_bitfield,
+            None => continue,
+        };
+        let flags = is_bitfield || registry.is_bitmask_group(&group.name);
+        let enums: Vec<&Enum> = registry.group_enums(&group.name).unwrap_or_else(Vec::new).into_iter()
+            // the other enums can't be converted to a constant of the group
+            .filter(|&e| {
+                match gen_enum_ty(registry, e) {
+                    Some("GLenum") | Some("GLbitfield") => !e.value.starts_with("-"),
+                    _ => false,
+                }
+            })
+            .collect();
+
+        try!(writeln!(dest, "
+            /// The {kind} of the `{name}` group.
+            #[repr(transparent)]
+            #[derive(Copy, Clone, PartialEq, Eq, Hash{debug})]
+            pub struct {name}(pub {base});
+
+            impl {name} {{",
+            kind = if flags { "flags" } else { "enums" },
+            name = group.name,
+            debug = if flags { ", Default" } else { ", Debug" },
+            base = if is_bitfield { "GLbitfield" } else { "GLenum" },
+        ));
+        for enm in enums.iter() {
+            try!(gen_deprecated(registry, options, &enm.ident, dest));
+            try!(writeln!(dest, "pub const {}: {} = {}({});",
+                          gen_enum_ident(enm), group.name, group.name, enm.value));
+        }
+        if flags {
+            try!(gen_flags_impl(&group.name, &enums, dest));
+        } else {
+            try!(writeln!(dest, "}}"));
         }
+    }
+
+    Ok(())
+}
+
+/// Writes the methods and the trait implementations of a group of flags, after its constants.
+fn gen_flags_impl<W>(name: &str, enums: &[&Enum], dest: &mut W) -> io::Result<()> where W: io::Write {
+    try!(writeln!(dest, "
+            /// Returns true if all the flags of `other` are set.
+            pub fn contains(&self, other: {name}) -> bool {{
+                self.0 & other.0 == other.0
+            }}
+
+            /// Returns true if no flag is set.
+            pub fn is_empty(&self) -> bool {{
+                self.0 == 0
+            }}
+        }}
+
+        impl ::std::ops::BitOr for {name} {{
+            type Output = {name};
+            fn bitor(self, other: {name}) -> {name} {{
+                {name}(self.0 | other.0)
+            }}
+        }}
 
-        Ns::Wgl => {
-            try!(ty::build_gl_aliases(dest));
-            try!(ty::build_win_aliases(dest));
-            try!(ty::build_wgl_aliases(dest));
+        impl ::std::ops::BitOrAssign for {name} {{
+            fn bitor_assign(&mut self, other: {name}) {{
+                self.0 |= other.0;
+            }}
+        }}
+
+        impl ::std::ops::BitAnd for {name} {{
+            type Output = {name};
+            fn bitand(self, other: {name}) -> {name} {{
+                {name}(self.0 & other.0)
+            }}
+        }}
+
+        impl ::std::ops::BitAndAssign for {name} {{
+            fn bitand_assign(&mut self, other: {name}) {{
+                self.0 &= other.0;
+            }}
+        }}
+
+        impl ::std::fmt::Debug for {name} {{
+            #[allow(deprecated)]
+            fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{
+                let flags: &[(&str, {name})] = &[{flags}];
+                let mut names = Vec::new();
+                let mut rest = self.0;
+                for &(flag_name, flag) in flags.iter() {{
+                    if flag.0 != 0 && rest & flag.0 == flag.0 {{
+                        names.push(flag_name.to_string());
+                        rest &= !flag.0;
+                    }}
+                }}
+                if rest != 0 || names.is_empty() {{
+                    names.push(format!(\"{{:#x}}\", rest));
+                }}
+                write!(fmt, \"{name}({{}})\", names.join(\" | \"))
+            }}
+        }}",
+        name = name,
+        flags = enums.iter()
+            .map(|e| format!("(\"{ident}\", {name}::{ident})", ident = gen_enum_ident(e), name = name))
+            .collect::<Vec<_>>().join(", "),
+    ));
+    Ok(())
+}
+
+/// Returns the enum group whose newtype a parameter takes, if `options` ask for enum groups and
+///  the parameter is a `GLenum` or a `GLbitfield` of a group that the registry defines.
+fn param_group<'a>(registry: &Registry, options: &Options, binding: &'a Binding) -> Option<&'a str> {
+    match binding.group {
+        Some(ref group) if options.enum_groups &&
+                           (binding.ty.trim() == "GLenum" || binding.ty.trim() == "GLbitfield") &&
+                           registry.groups.iter().any(|g| &g.name == group) => Some(group),
+        _ => None,
+    }
+}
+
+/// Generates the `equivalent_enums` of the registry, documented with the enum they are
+///  equivalent to, if `options` ask for them.
+pub fn gen_equivalent_enums<W>(registry: &Registry, options: &Options, types_prefix: &str, dest: &mut W)
+                               -> io::Result<()> where W: io::Write
+{
+    if !options.enum_aliases {
+        return Ok(());
+    }
+    for equivalent in registry.equivalent_enums.iter() {
+        try!(writeln!(dest, "/// Equivalent to `{}`.", equivalent.equivalent_to));
+        try!(gen_enum_item(registry, &equivalent.enm, types_prefix, dest));
+    }
+    Ok(())
+}
+
+/// If the value of an enum has the form `((Type)Value)`, returns `(Type, Value)`.
+fn split_enum_cast(value: &str) -> Option<(&str, &str)> {
+    if value.starts_with("((") && value.ends_with(")") {
+        let separator = value.chars().skip(2).position(|c| c == ')').unwrap();
+        Some((&value[2 .. separator + 2], value[separator + 3 ..].trim_matches(')')))
+    } else {
+        None
+    }
+}
+
+/// Returns the name of the type from the `types` module of an enum, or `None` if the enum is a
+///  string.
+fn gen_enum_ty<'a>(registry: &Registry, enm: &'a Enum) -> Option<&'a str> {
+    // some enums have a value of the form `((Type)Value)` ; if this is the case, we need to
+    //  replace the type of the enum (which is GLenum by default) by the type in the expression
+    if let Some((ty, _)) = split_enum_cast(&enm.value) {
+        Some(ty)
+    } else if enm.value.starts_with("\"") {
+        None
+    } else {
+        // some values are `TRUE` or `FALSE`, in which case we use `GLboolean` instead of
+        //  `GLenum`
+        match &enm.ident[..] {
+            "TRUE" | "FALSE" => Some("GLboolean"),
+            _ => match enm.ty {
+                Some(ref s) if &s[..] == "ull" => Some("GLuint64"),
+                // the values of the `<enums type="bitmask">` blocks are combined into a bitfield
+                _ if registry.enum_ns(enm).is_bitmask() &&
+                     registry.types.iter().any(|t| t.name == "GLbitfield") => Some("GLbitfield"),
+                _ => Some("GLenum"),
+            }
         }
+    }
+}
+
+/// Generates the definitions of the types used by the commands and enums of a registry.
+///
+/// Definitions are either `pub type = ...` or `#[repr(C)] pub struct ... { ... }`, and are
+///  obtained from the `<types>` section of the registry when possible. Only the types that are
+///  actually referenced are generated, along with the types they depend on. `extra` contains the
+///  names of the types that the generator itself needs.
+pub fn gen_type_aliases<W>(registry: &Registry, extra: &[&str], dest: &mut W) -> io::Result<()> where W: io::Write {
+    let mut pending: Vec<(String, bool)> = extra.iter().map(|ty| (ty.to_string(), false)).collect();
+    for cmd in registry.cmd_iter() {
+        pending.extend(ty::type_dep(&cmd.proto.ty).map(|ty| (ty, false)));
+        for param in cmd.params.iter() {
+            pending.extend(ty::type_dep(&param.ty).map(|ty| (ty, false)));
+        }
+    }
+    for enm in registry.enum_iter().chain(registry.equivalent_enums.iter().map(|e| &e.enm)) {
+        pending.extend(gen_enum_ty(registry, enm).map(|ty| (ty.to_string(), false)));
+    }
 
-        Ns::Egl => {
-            try!(ty::build_gl_aliases(dest));
-            try!(ty::build_egl_aliases(dest));
+    // sorting the definitions by name keeps the output stable
+    let mut defs = BTreeMap::new();
+    while let Some((name, is_struct)) = pending.pop() {
+        if defs.contains_key(&name) {
+            continue;
+        }
+        match ty::gen_type_def(registry, &name, is_struct) {
+            Some((def, deps)) => {
+                defs.insert(name, def);
+                pending.extend(deps);
+            }
+            None => return Err(io::Error::new(io::ErrorKind::Other,
+                                              format!("no definition for type `{}`", name))),
         }
     }
 
+    for def in defs.values() {
+        try!(writeln!(dest, "{}", def));
+    }
+
     Ok(())
 }
 
+/// Returns the first type that `ty` depends on that has no definition, if any.
+fn find_unknown_type(registry: &Registry, ty: &str) -> Option<String> {
+    let mut pending = match ty::type_dep(ty) {
+        Some(dep) => vec![(dep, false)],
+        None if ty::to_rust_ty(ty).is_none() => return Some(ty.to_string()),
+        None => return None,
+    };
+    let mut done = HashSet::new();
+    while let Some((name, is_struct)) = pending.pop() {
+        if !done.insert(name.clone()) {
+            continue;
+        }
+        match ty::gen_type_def(registry, &name, is_struct) {
+            Some((_, deps)) => pending.extend(deps),
+            None => return Some(name),
+        }
+    }
+    None
+}
+
+/// Checks that every type used by the commands of the registry can be converted to a Rust type.
+///
+/// `gen_parameters`, `gen_return_type` and `gen_type_aliases` fail on unknown types, so this
+///  should be called before handing the registry to a generator.
+pub fn check_types(registry: &Registry) -> Result<(), Error> {
+    for cmd in registry.cmd_iter() {
+        if let Some(ty) = find_unknown_type(registry, &cmd.proto.ty) {
+            return Err(Error::UnknownType {
+                ty: ty,
+                cmd: cmd.proto.ident.clone(),
+                param: None,
+            });
+        }
+
+        for param in cmd.params.iter() {
+            if let Some(ty) = find_unknown_type(registry, &param.ty) {
+                return Err(Error::UnknownType {
+                    ty: ty,
+                    cmd: cmd.proto.ident.clone(),
+                    param: Some(param.ident.clone()),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn expect_rust_ty(ty: &str) -> String {
+    match ty::to_rust_ty(ty) {
+        Some(ty) => ty,
+        None => panic!("Type conversion not implemented for `{}`", ty),
+    }
+}
+
 /// Generates the list of Rust `Arg`s that a `Cmd` requires.
-pub fn gen_parameters(cmd: &Cmd, with_idents: bool, with_types: bool) -> Vec<String> {
+///
+/// The parameters of an enum group take the newtype of the group if `options` ask for it. See
+///  `gen_enum_groups`.
+pub fn gen_parameters(registry: &Registry, options: &Options, cmd: &Cmd, with_idents: bool,
+                      with_types: bool) -> Vec<String>
+{
     cmd.params.iter()
         .map(|binding| {
             // variable name of the binding
@@ -122,7 +435,10 @@ pub fn gen_parameters(cmd: &Cmd, with_idents: bool, with_types: bool) -> Vec<Str
             };
 
             // rust type of the binding
-            let ty = ty::to_rust_ty(&binding.ty[..]);
+            let ty = match param_group(registry, options, binding) {
+                Some(group) => format!("types::{}", group),
+                None => expect_rust_ty(&binding.ty),
+            };
 
             // returning
             if with_idents && with_types {
@@ -141,24 +457,97 @@ pub fn gen_parameters(cmd: &Cmd, with_idents: bool, with_types: bool) -> Vec<Str
 /// Generates the Rust return type of a `Cmd`.
 pub fn gen_return_type(cmd: &Cmd) -> String {
     // turn the return type into a Rust type
-    let ty = ty::to_rust_ty(&cmd.proto.ty);
+    let ty = expect_rust_ty(&cmd.proto.ty);
 
     // ... but there is one more step: if the Rust type is `c_void`, we replace it with `()`
     if ty == "__gl_imports::libc::c_void" {
         return "()".to_string();
     }
 
-    ty.to_string()
+    ty
+}
+
+/// Writes the `arrays` module if `options` ask for it. It has a helper for each command that is
+///  the vector equivalent of another one (see `Registry::vector_equivalent`) and whose array has a
+///  constant length, like `Color3fv(v: &[types::GLfloat; 3])` for `Color3fv(v: *const GLfloat)`.
+///
+/// `receiver` is the name of the struct that has the commands as methods, in which case the
+///  helpers take it as their first argument, or `None` if the commands are functions.
+pub fn gen_vec_helpers<W>(registry: &Registry, options: &Options, receiver: Option<&str>, dest: &mut W)
+                          -> io::Result<()> where W: io::Write
+{
+    if !options.vec_helpers {
+        return Ok(());
+    }
+
+    let mut scalars: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for c in registry.cmd_iter() {
+        if let Some(ref vector) = c.vecequiv {
+            scalars.entry(&vector[..]).or_insert_with(Vec::new).push(&c.proto.ident);
+        }
+    }
+
+    try!(writeln!(dest, "
+        #[allow(non_snake_case, dead_code)]
+        pub mod arrays {{
+            #[allow(unused_imports)] use super::{{__gl_imports, types}};"));
+
+    for c in registry.cmd_iter() {
+        let scalars = match scalars.get(&c.proto.ident[..]) {
+            Some(scalars) => scalars,
+            None => continue,
+        };
+        let (pos, len, elem) = match find_array_param(c) {
+            Some(array) => array,
+            None => continue,
+        };
+
+        let mut params = gen_parameters(registry, options, c, true, true);
+        let mut idents = gen_parameters(registry, options, c, true, false);
+        params[pos] = format!("{}: &[{}; {}]", idents[pos], elem, len);
+        idents[pos] = format!("{}.as_ptr()", idents[pos]);
+        let (receiver_param, call) = match receiver {
+            Some(receiver) => (vec![format!("gl: &super::{}", receiver)], format!("gl.{}", c.proto.ident)),
+            None => (vec![], format!("super::{}", c.proto.ident)),
+        };
+
+        try!(writeln!(dest, "/// Calls `{}` with an array. Vector equivalent of `{}`.",
+                      c.proto.ident, scalars.join("`, `")));
+        try!(gen_deprecated(registry, options, &c.proto.ident, dest));
+        try!(writeln!(dest,
+            "#[inline] pub unsafe fn {name}({params}) -> {return_suffix} {{ {call}({idents}) }}",
+            name = c.proto.ident,
+            params = receiver_param.into_iter().chain(params.into_iter()).collect::<Vec<_>>().join(", "),
+            return_suffix = gen_return_type(c),
+            call = call,
+            idents = idents.join(", "),
+        ));
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Returns the position, the length and the Rust element type of the only parameter of a command
+///  that is a pointer to a constant number of elements.
+fn find_array_param(cmd: &Cmd) -> Option<(usize, u32, String)> {
+    let mut arrays = cmd.params.iter().enumerate().filter_map(|(i, p)| {
+        let ty = expect_rust_ty(&p.ty);
+        match p.len {
+            Some(Len::Constant(len)) if ty.starts_with("*const ") && !ty[7..].contains('*') => {
+                Some((i, len, ty[7..].to_string()))
+            }
+            _ => None,
+        }
+    });
+    match (arrays.next(), arrays.next()) {
+        (Some(array), None) => Some(array),
+        _ => None,
+    }
 }
 
 /// Generates the native symbol name of a `Cmd`.
 ///
 /// Example results: `"glClear"`, `"wglCreateContext"`, etc.
 pub fn gen_symbol_name(ns: &Ns, cmd: &str) -> String {
-    (match *ns {
-        Ns::Gl | Ns::Gles1 | Ns::Gles2 => "gl",
-        Ns::Glx => "glX",
-        Ns::Wgl => "wgl",
-        Ns::Egl => "egl",
-    }).to_string() + cmd
+    ns.info().cmd_prefix.to_string() + cmd
 }
@@ -0,0 +1,359 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::{Cmd, Len, Registry, Ns};
+use std::io;
+use super::Options;
+use super::struct_gen::StructGenerator;
+
+/// Writes the bindings of `StructGenerator`, plus a safe method for each command whose pointers
+///  can be described with slices and references, like `gen_buffers(&mut [GLuint])` for
+///  `GenBuffers(n, *mut GLuint)`.
+///
+/// The lengths of the arrays come from the `len` attributes of the registry. The commands that
+///  take pointers without a known length, like `MapBuffer` or `GetIntegerv`, or handles like
+///  `GLsync`, only have their `unsafe` method. So do the commands that keep their pointers after
+///  they return, like `VertexAttribPointer` or `FeedbackBuffer`, and the ones that read indices,
+///  which can be an offset into a buffer.
+#[allow(missing_copy_implementations)]
+pub struct SafeWrapperGenerator;
+
+impl super::Generator for SafeWrapperGenerator {
+    fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
+        try!(StructGenerator.write_with_options(registry, ns, options, dest));
+        try!(write_safe_impl(registry, &ns, options, dest));
+        Ok(())
+    }
+}
+
+/// How the safe method of a command passes one of its parameters.
+#[derive(Clone, Debug, PartialEq)]
+enum Arg {
+    /// Taken and passed as-is.
+    Value,
+    /// Not taken: the length of the arrays that name it, as the Rust type of the parameter.
+    Len(String),
+    /// A pointer to `len` elements, taken as a reference if `len` is 1, as a reference to an
+    ///  array otherwise.
+    Array { mutable: bool, elem: String, len: u32 },
+    /// A pointer to a number of elements given by another parameter, taken as a slice.
+    Slice { mutable: bool, elem: String, factor: u32 },
+    /// A null-terminated string, taken as a `&CStr`.
+    CStr,
+    /// A list of null-terminated strings, taken as a `&[&CStr]`.
+    CStrs,
+    /// A list of strings whose lengths are passed in another parameter, taken as a `&[&str]`.
+    Strs { lengths: usize },
+    /// Not taken: the lengths of the strings of a `Strs`, as the Rust type of their elements.
+    Lengths(String),
+}
+
+/// Types that can be passed by value or pointed to without any other requirement.
+const PLAIN_TYPES: &'static [&'static str] = &[
+    "GLenum", "GLboolean", "GLbitfield", "GLbyte", "GLshort", "GLint", "GLubyte", "GLushort",
+    "GLuint", "GLsizei", "GLfloat", "GLclampf", "GLdouble", "GLclampd", "GLchar", "GLcharARB",
+    "GLint64", "GLuint64", "GLint64EXT", "GLuint64EXT", "GLintptr", "GLsizeiptr", "GLintptrARB",
+    "GLsizeiptrARB", "GLfixed", "GLclampx", "GLhalf", "GLhalfARB", "GLhalfNV",
+];
+
+/// Types that a parameter naming the length of an array can have.
+const LEN_TYPES: &'static [&'static str] = &[
+    "GLint", "GLuint", "GLsizei", "GLintptr", "GLsizeiptr", "GLintptrARB", "GLsizeiptrARB",
+];
+
+/// Types and names of the parameters that give the length of a `void` pointer in bytes.
+const BYTE_LEN_TYPES: &'static [&'static str] = &["GLsizeiptr", "GLsizeiptrARB"];
+const BYTE_LEN_NAMES: &'static [&'static str] = &["size", "imageSize", "bufSize"];
+
+/// Parts of the names of the commands that keep their pointers after they return, or that read
+///  indices, which the `len` attributes don't tell.
+const UNSAFE_COMMANDS: &'static [&'static str] = &[
+    "Pointer", "FeedbackBuffer", "SelectBuffer", "DebugMessageCallback", "DrawElements",
+    "DrawRangeElements",
+];
+
+/// Returns true if a command can only have its `unsafe` method, whatever its parameters.
+fn is_unsafe_only(cmd: &Cmd) -> bool {
+    UNSAFE_COMMANDS.iter().any(|part| cmd.proto.ident.contains(part)) ||
+        cmd.params.iter().any(|p| p.ident == "indices")
+}
+
+fn is_types_item(ty: &str, names: &[&str]) -> bool {
+    ty.starts_with("types::") && names.contains(&&ty["types::".len()..])
+}
+
+/// Returns the element type of a pointer to a plain type, with `void` pointing to bytes.
+fn pointee(ty: &str) -> Option<(bool, String)> {
+    let (mutable, elem) = if ty.starts_with("*const ") {
+        (false, &ty["*const ".len()..])
+    } else if ty.starts_with("*mut ") {
+        (true, &ty["*mut ".len()..])
+    } else {
+        return None;
+    };
+    if elem == "__gl_imports::libc::c_void" {
+        Some((mutable, "u8".to_string()))
+    } else if is_types_item(elem, PLAIN_TYPES) {
+        Some((mutable, elem.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Returns true if the parameter `name` of a command is a length in bytes.
+fn is_byte_len(cmd: &Cmd, name: &str, factor: u32) -> bool {
+    factor == 1 && cmd.params.iter().any(|p| {
+        p.ident == name && (BYTE_LEN_NAMES.contains(&name) || BYTE_LEN_TYPES.contains(&&p.ty[..]))
+    })
+}
+
+/// Returns how the safe method of a command takes each of its parameters, or `None` if the
+///  command can't be called safely.
+fn safe_args(registry: &Registry, options: &Options, cmd: &Cmd) -> Option<Vec<Arg>> {
+    if is_unsafe_only(cmd) {
+        return None;
+    }
+    let tys = super::gen_parameters(registry, options, cmd, false, true);
+    let mut args = Vec::with_capacity(cmd.params.len());
+    let mut lens = Vec::new();
+
+    for (param, ty) in cmd.params.iter().zip(tys.iter()) {
+        let arg = match param.len {
+            // the parameters of an enum group take its newtype
+            _ if !ty.contains('*') => {
+                if is_types_item(ty, PLAIN_TYPES) || super::param_group(registry, options, param).is_some() {
+                    Arg::Value
+                } else {
+                    return None;
+                }
+            }
+            // the registry doesn't give the length of most of the names
+            Some(Len::NullTerminated) | None if ty == "*const types::GLchar" => Arg::CStr,
+            Some(Len::Param { ref name, factor: 1 }) if ty == "*const *const types::GLchar" => {
+                lens.push(name);
+                let lengths = cmd.params.iter().position(|p| {
+                    p.ident == "length" && p.len == param.len && p.ty.contains("GLint")
+                });
+                match lengths {
+                    Some(lengths) => Arg::Strs { lengths: lengths },
+                    None => Arg::CStrs,
+                }
+            }
+            Some(Len::Constant(len)) => match pointee(ty) {
+                Some((mutable, elem)) => Arg::Array { mutable: mutable, elem: elem, len: len },
+                None => return None,
+            },
+            Some(Len::Param { ref name, factor }) => match pointee(ty) {
+                // a slice of bytes only fills a length in bytes
+                Some((_, ref elem)) if elem == "u8" && !is_byte_len(cmd, name, factor) => return None,
+                Some((mutable, elem)) => {
+                    lens.push(name);
+                    Arg::Slice { mutable: mutable, elem: elem, factor: factor }
+                }
+                None => return None,
+            },
+            _ => return None,
+        };
+        args.push(arg);
+    }
+
+    // the lengths of the strings are computed from the strings
+    for i in 0 .. args.len() {
+        if let Arg::Strs { lengths } = args[i] {
+            match args[lengths].clone() {
+                Arg::Slice { mutable: false, elem, factor: 1 } => args[lengths] = Arg::Lengths(elem),
+                _ => return None,
+            }
+        }
+    }
+
+    // the lengths of the arrays are computed from the slices
+    for name in lens {
+        let pos = match cmd.params.iter().position(|p| &p.ident == name) {
+            Some(pos) => pos,
+            None => return None,
+        };
+        match args[pos] {
+            Arg::Value if is_types_item(&tys[pos], LEN_TYPES) => args[pos] = Arg::Len(tys[pos].clone()),
+            Arg::Len(_) => (),
+            _ => return None,
+        }
+    }
+
+    Some(args)
+}
+
+/// Returns the name of the safe method of a command, like `gen_buffers` for `GenBuffers`.
+fn gen_safe_name(ident: &str) -> String {
+    let mut name = String::with_capacity(ident.len() + 4);
+    let mut prev_lower = false;
+    for c in ident.chars() {
+        if c.is_uppercase() && prev_lower {
+            name.push('_');
+        }
+        prev_lower = c.is_lowercase();
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+fn gen_borrow(mutable: bool) -> &'static str {
+    if mutable { "&mut " } else { "&" }
+}
+
+fn gen_ptr(mutable: bool) -> &'static str {
+    if mutable { "*mut" } else { "*const" }
+}
+
+/// Casts the pointer to bytes that replaces a `void` pointer.
+fn gen_cast(mutable: bool) -> String {
+    format!(" as {} _", gen_ptr(mutable))
+}
+
+/// Returns the pointer to the elements of a slice or an array.
+fn gen_as_ptr(ident: &str, mutable: bool, elem: &str) -> String {
+    format!("{}.{}(){}", ident, if mutable { "as_mut_ptr" } else { "as_ptr" },
+            if elem == "u8" { gen_cast(mutable) } else { String::new() })
+}
+
+/// Returns the expression that computes the parameter `pos` of a command from the arrays whose
+///  length it is: the number of elements of the shortest one.
+fn gen_len(cmd: &Cmd, args: &[Arg], idents: &[String], pos: usize) -> String {
+    let mut lens = Vec::new();
+    for (i, param) in cmd.params.iter().enumerate() {
+        let factor = match param.len {
+            Some(Len::Param { ref name, factor }) if *name == cmd.params[pos].ident => factor,
+            _ => continue,
+        };
+        match args[i] {
+            Arg::Slice { .. } if factor != 1 => lens.push(format!("({}.len() / {})", idents[i], factor)),
+            Arg::Slice { .. } | Arg::CStrs | Arg::Strs { .. } => lens.push(format!("{}.len()", idents[i])),
+            _ => (),
+        }
+    }
+    let first = lens.remove(0);
+    lens.into_iter().fold(first, |len, next| format!("::std::cmp::min({}, {})", len, next))
+}
+
+/// Creates a second `impl` of the structure of `StructGenerator`, with the safe methods.
+fn write_safe_impl<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()>
+                      where W: io::Write
+{
+    try!(writeln!(dest, "impl {ns} {{", ns = ns.fmt_struct_name()));
+
+    for c in registry.cmd_iter() {
+        // only the strings can be returned as references
+        let return_suffix = super::gen_return_type(c);
+        let returns_str = return_suffix == "*const types::GLubyte";
+        if return_suffix.contains('*') && !returns_str {
+            continue;
+        }
+        let args = match safe_args(registry, options, c) {
+            Some(args) => args,
+            None => continue,
+        };
+
+        let idents = super::gen_parameters(registry, options, c, true, false);
+        let tys = super::gen_parameters(registry, options, c, false, true);
+        let mut params = Vec::new();
+        let mut locals = Vec::new();
+        let mut call_args = Vec::new();
+
+        for (i, arg) in args.iter().enumerate() {
+            let ident = &idents[i];
+            let (param, call_arg) = match *arg {
+                Arg::Value => (Some(format!("{}: {}", ident, tys[i])), ident.clone()),
+                Arg::Len(ref ty) => (None, format!("{} as {}", gen_len(c, &args, &idents, i), ty)),
+                Arg::Array { mutable, ref elem, len } if len == 1 => {
+                    let call_arg = if elem == "u8" {
+                        format!("{} as {} u8{}", ident, gen_ptr(mutable), gen_cast(mutable))
+                    } else {
+                        ident.clone()
+                    };
+                    (Some(format!("{}: {}{}", ident, gen_borrow(mutable), elem)), call_arg)
+                }
+                Arg::Array { mutable, ref elem, len } => {
+                    (Some(format!("{}: {}[{}; {}]", ident, gen_borrow(mutable), elem, len)),
+                     gen_as_ptr(ident, mutable, elem))
+                }
+                Arg::Slice { mutable, ref elem, .. } => {
+                    (Some(format!("{}: {}[{}]", ident, gen_borrow(mutable), elem)),
+                     gen_as_ptr(ident, mutable, elem))
+                }
+                Arg::CStr => (Some(format!("{}: &::std::ffi::CStr", ident)), format!("{}.as_ptr()", ident)),
+                Arg::CStrs => {
+                    locals.push(format!(
+                        "let {i}_ptrs: Vec<*const types::GLchar> = {i}.iter().map(|s| s.as_ptr()).collect();",
+                        i = ident));
+                    (Some(format!("{}: &[&::std::ffi::CStr]", ident)), format!("{}_ptrs.as_ptr()", ident))
+                }
+                Arg::Strs { lengths } => {
+                    let elem = match args[lengths] {
+                        Arg::Lengths(ref elem) => elem,
+                        _ => unreachable!(),
+                    };
+                    locals.push(format!(
+                        "let {i}_ptrs: Vec<*const types::GLchar> = \
+                         {i}.iter().map(|s| s.as_ptr() as *const types::GLchar).collect();",
+                        i = ident));
+                    // a negative length would make the string null-terminated
+                    locals.push(format!(
+                        "let {i}_lengths: Vec<{elem}> = {i}.iter()\
+                         .map(|s| ::std::cmp::min(s.len(), {elem}::max_value() as usize) as {elem}).collect();",
+                        i = ident, elem = elem));
+                    (Some(format!("{}: &[&str]", ident)), format!("{}_ptrs.as_ptr()", ident))
+                }
+                Arg::Lengths(_) => {
+                    let strs = args.iter().position(|a| *a == Arg::Strs { lengths: i }).unwrap();
+                    (None, format!("{}_lengths.as_ptr()", idents[strs]))
+                }
+            };
+            params.extend(param);
+            call_args.push(call_arg);
+        }
+
+        let call = format!("self.{}({})", c.proto.ident, call_args.join(", "));
+        let (return_suffix, body) = if returns_str {
+            ("Option<&::std::ffi::CStr>".to_string(),
+             format!("let ptr = {}; \
+                      if ptr.is_null() {{ None }} else {{ Some(::std::ffi::CStr::from_ptr(ptr as *const _)) }}",
+                     call))
+        } else {
+            (return_suffix, call)
+        };
+
+        try!(writeln!(dest, "/// Safe version of `{}`.", c.proto.ident));
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
+        try!(writeln!(dest,
+            "#[allow(dead_code)] #[allow(deprecated)]
+            #[inline] pub fn {name}(&self{params}) -> {return_suffix} {{ \
+                {locals} unsafe {{ {body} }} \
+            }}",
+            name = gen_safe_name(&c.proto.ident),
+            params = params.iter().map(|p| format!(", {}", p)).collect::<String>(),
+            return_suffix = return_suffix,
+            locals = locals.join(" "),
+            body = body,
+        ));
+    }
+
+    writeln!(dest, "}}")
+}
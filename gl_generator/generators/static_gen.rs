@@ -15,16 +15,24 @@
 
 use registry::{Registry, Ns};
 use std::io;
+use super::Options;
 
 #[allow(missing_copy_implementations)]
 pub struct StaticGenerator;
 
 impl super::Generator for StaticGenerator {
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
         try!(write_header(dest));
-        try!(write_type_aliases(&ns, dest));
-        try!(write_enums(registry, dest));
-        try!(write_fns(registry, &ns, dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
+        try!(write_fns(registry, &ns, options, dest));
+        try!(super::gen_vec_helpers(registry, options, None, dest));
         Ok(())
     }
 }
@@ -43,7 +51,7 @@ fn write_header<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_type_aliases`.
-fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, r#"
         pub mod types {{
             #![allow(non_camel_case_types)]
@@ -52,7 +60,8 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
             #![allow(missing_copy_implementations)]
     "#));
 
-    try!(super::gen_type_aliases(ns, dest));
+    try!(super::gen_type_aliases(registry, &[], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
 
     writeln!(dest, "
         }}
@@ -60,29 +69,31 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for e in registry.enum_iter() {
-        try!(super::gen_enum_item(e, "types::", dest));
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
     }
 
-    Ok(())
+    super::gen_equivalent_enums(registry, options, "types::", dest)
 }
 
 /// io::Writes all functions corresponding to the GL bindings.
 ///
 /// These are foreign functions, they don't have any content.
-fn write_fns<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_fns<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, "
         #[allow(non_snake_case, unused_variables, dead_code)]
         extern \"system\" {{"));
 
     for c in registry.cmd_iter() {
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
         try!(writeln!(dest,
             "#[link_name=\"{symbol}\"]
             pub fn {name}({params}) -> {return_suffix};",
             symbol = super::gen_symbol_name(ns, &c.proto.ident),
             name = c.proto.ident,
-            params = super::gen_parameters(c, true, true).join(", "),
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
             return_suffix = super::gen_return_type(c)
         ));
     }
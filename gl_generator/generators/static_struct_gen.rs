@@ -15,18 +15,26 @@
 
 use registry::{Registry, Ns};
 use std::io;
+use super::Options;
 
 #[allow(missing_copy_implementations)]
 pub struct StaticStructGenerator;
 
 impl super::Generator for StaticStructGenerator {
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
         try!(write_header(dest));
-        try!(write_type_aliases(&ns, dest));
-        try!(write_enums(registry, dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
         try!(write_struct(&ns, dest));
-        try!(write_impl(registry, &ns, dest));
-        try!(write_fns(registry, &ns, dest));
+        try!(write_impl(registry, &ns, options, dest));
+        try!(write_fns(registry, &ns, options, dest));
+        try!(super::gen_vec_helpers(registry, options, Some(ns.fmt_struct_name()), dest));
         Ok(())
     }
 }
@@ -45,7 +53,7 @@ fn write_header<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_type_aliases`.
-fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, r#"
         pub mod types {{
             #![allow(non_camel_case_types)]
@@ -54,18 +62,20 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
             #![allow(missing_copy_implementations)]
     "#));
 
-    try!(super::gen_type_aliases(ns, dest));
+    try!(super::gen_type_aliases(registry, &[], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for e in registry.enum_iter() {
-        try!(super::gen_enum_item(e, "types::", dest));
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
     }
 
-    Ok(())
+    super::gen_equivalent_enums(registry, options, "types::", dest)
 }
 
 /// Creates a stub structure.
@@ -83,7 +93,7 @@ fn write_struct<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_impl<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest,
         "impl {ns} {{
             /// Stub function.
@@ -95,6 +105,7 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
     ));
 
     for c in registry.cmd_iter() {
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
         try!(writeln!(dest,
             "#[allow(non_snake_case)]
             // #[allow(unused_variables)]
@@ -104,9 +115,9 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
                 {name}({idents})
             }}",
             name = c.proto.ident,
-            typed_params = super::gen_parameters(c, true, true).join(", "),
+            typed_params = super::gen_parameters(registry, options, c, true, true).join(", "),
             return_suffix = super::gen_return_type(c),
-            idents = super::gen_parameters(c, true, false).join(", "),
+            idents = super::gen_parameters(registry, options, c, true, false).join(", "),
         ));
     }
 
@@ -116,7 +127,7 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
 /// io::Writes all functions corresponding to the GL bindings.
 ///
 /// These are foreign functions, they don't have any content.
-fn write_fns<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_fns<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
 
     try!(writeln!(dest, "
         #[allow(non_snake_case)]
@@ -129,7 +140,7 @@ fn write_fns<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> wh
             "#[link_name=\"{symbol}\"] fn {name}({params}) -> {return_suffix};",
             symbol = super::gen_symbol_name(ns, &c.proto.ident),
             name = c.proto.ident,
-            params = super::gen_parameters(c, true, true).join(", "),
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
             return_suffix = super::gen_return_type(c)
         ));
     }
@@ -15,19 +15,27 @@
 
 use registry::{Registry, Ns};
 use std::io;
+use super::Options;
 
 #[allow(missing_copy_implementations)]
 pub struct StructGenerator;
 
 impl super::Generator for StructGenerator {
     fn write<W>(&self, registry: &Registry, ns: Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+        self.write_with_options(registry, ns, &Options::default(), dest)
+    }
+
+    fn write_with_options<W>(&self, registry: &Registry, ns: Ns, options: &Options, dest: &mut W)
+                             -> io::Result<()> where W: io::Write
+    {
         try!(write_header(dest));
-        try!(write_type_aliases(&ns, dest));
-        try!(write_enums(registry, dest));
+        try!(write_type_aliases(registry, options, dest));
+        try!(write_enums(registry, options, dest));
         try!(write_fnptr_struct_def(dest));
         try!(write_panicking_fns(&ns, dest));
         try!(write_struct(registry, &ns, dest));
-        try!(write_impl(registry, &ns, dest));
+        try!(write_impl(registry, &ns, options, dest));
+        try!(super::gen_vec_helpers(registry, options, Some(ns.fmt_struct_name()), dest));
         Ok(())
     }
 }
@@ -48,7 +56,7 @@ fn write_header<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_type_aliases`.
-fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_type_aliases<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest, r#"
         pub mod types {{
             #![allow(non_camel_case_types)]
@@ -57,18 +65,20 @@ fn write_type_aliases<W>(ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::W
             #![allow(missing_copy_implementations)]
     "#));
 
-    try!(super::gen_type_aliases(ns, dest));
+    try!(super::gen_type_aliases(registry, &[], dest));
+    try!(super::gen_enum_groups(registry, options, dest));
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_enums<W>(registry: &Registry, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     for e in registry.enum_iter() {
-        try!(super::gen_enum_item(e, "types::", dest));
+        try!(super::gen_deprecated(registry, options, &e.ident, dest));
+        try!(super::gen_enum_item(registry, e, "types::", dest));
     }
 
-    Ok(())
+    super::gen_equivalent_enums(registry, options, "types::", dest)
 }
 
 /// Creates a `FnPtr` structure which contains the store for a single binding.
@@ -137,8 +147,9 @@ fn write_struct<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()>
     ));
 
     for c in registry.cmd_iter() {
-        if let Some(v) = registry.aliases.get(&c.proto.ident) {
-            try!(writeln!(dest, "/// Fallbacks: {}", v.join(", ")));
+        let fallbacks = registry.fallbacks(&c.proto.ident);
+        if !fallbacks.is_empty() {
+            try!(writeln!(dest, "/// Fallbacks: {}", fallbacks.join(", ")));
         }
         try!(writeln!(dest, "pub {name}: FnPtr,", name = c.proto.ident));
     }
@@ -147,7 +158,7 @@ fn write_struct<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()>
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> where W: io::Write {
+fn write_impl<W>(registry: &Registry, ns: &Ns, options: &Options, dest: &mut W) -> io::Result<()> where W: io::Write {
     try!(writeln!(dest,
         "impl {ns} {{
             /// Load each OpenGL symbol using a custom load function. This allows for the
@@ -178,14 +189,9 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
             "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}])),",
             name = c.proto.ident,
             symbol = super::gen_symbol_name(ns, &c.proto.ident),
-            fallbacks = match registry.aliases.get(&c.proto.ident) {
-                Some(fbs) => {
-                    fbs.iter()
-                       .map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name)))
-                       .collect::<Vec<_>>().join(", ")
-                },
-                None => format!(""),
-            },
+            fallbacks = registry.fallbacks(&c.proto.ident).iter()
+                .map(|name| format!("\"{}\"", super::gen_symbol_name(ns, &name)))
+                .collect::<Vec<_>>().join(", "),
         ))
     }
 
@@ -207,6 +213,7 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
     ));
 
     for c in registry.cmd_iter() {
+        try!(super::gen_deprecated(registry, options, &c.proto.ident, dest));
         try!(writeln!(dest,
             "#[allow(non_snake_case)] #[allow(unused_variables)] #[allow(dead_code)]
             #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
@@ -214,10 +221,10 @@ fn write_impl<W>(registry: &Registry, ns: &Ns, dest: &mut W) -> io::Result<()> w
                     (self.{name}.f)({idents}) \
             }}",
             name = c.proto.ident,
-            params = super::gen_parameters(c, true, true).join(", "),
-            typed_params = super::gen_parameters(c, false, true).join(", "),
+            params = super::gen_parameters(registry, options, c, true, true).join(", "),
+            typed_params = super::gen_parameters(registry, options, c, false, true).join(", "),
             return_suffix = super::gen_return_type(c),
-            idents = super::gen_parameters(c, true, false).join(", "),
+            idents = super::gen_parameters(registry, options, c, true, false).join(", "),
         ))
     }
 
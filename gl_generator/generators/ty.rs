@@ -13,745 +13,521 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io;
+//! Conversion of the C types found in the registry to Rust types.
+//!
+//! Most type definitions are generated from the `<types>` section of the registry. The few that
+//!  can't be, either because they come from a platform header (`Display`, `HDC`, ...) or because
+//!  the registry uses the preprocessor to define them, are kept in the tables at the bottom of
+//!  this file.
+
+use registry::{Registry, Type, TypeDef};
+
+/// A definition that is not generated from the registry.
+struct Builtin {
+    name: &'static str,
+    /// The other types used by the definition.
+    deps: &'static [&'static str],
+    /// The Rust code, as it appears in the `types` module.
+    def: &'static str,
+}
 
-/// Converts a C style type definition to the Rust equivalent
-pub fn to_rust_ty(ty: &str) -> &'static str {
-    match ty {
-        // gl.xml types
-        "GLDEBUGPROC"               => "types::GLDEBUGPROC",
-        "GLDEBUGPROCAMD"            => "types::GLDEBUGPROCAMD",
-        "GLDEBUGPROCARB"            => "types::GLDEBUGPROCARB",
-        "GLDEBUGPROCKHR"            => "types::GLDEBUGPROCKHR",
-        "GLbitfield"                => "types::GLbitfield",
-        "GLboolean"                 => "types::GLboolean",
-        "GLbyte"                    => "types::GLbyte",
-        "GLclampd"                  => "types::GLclampd",
-        "GLclampf"                  => "types::GLclampf",
-        "GLclampx"                  => "types::GLclampx",
-        "GLdouble"                  => "types::GLdouble",
-        "GLeglImageOES"             => "types::GLeglImageOES",
-        "GLenum"                    => "types::GLenum",
-        "GLfixed"                   => "types::GLfixed",
-        "GLfloat"                   => "types::GLfloat",
-        "GLhalfNV"                  => "types::GLhalfNV",
-        "GLhandleARB"               => "types::GLhandleARB",
-        "GLint"                     => "types::GLint",
-        "GLint64EXT"                => "types::GLint64EXT",
-        "GLintptr"                  => "types::GLintptr",
-        "GLintptrARB"               => "types::GLintptrARB",
-        "GLshort"                   => "types::GLshort",
-        "GLsizei"                   => "types::GLsizei",
-        "GLsizeiptr"                => "types::GLsizeiptr",
-        "GLsizeiptrARB"             => "types::GLsizeiptrARB",
-        "GLsync"                    => "types::GLsync",
-        "GLubyte"                   => "types::GLubyte",
-        "GLuint"                    => "types::GLuint",
-        "GLuint64"                  => "types::GLuint64",
-        "GLuint64EXT"               => "types::GLuint64EXT",
-        "GLushort"                  => "types::GLushort",
-        "GLvdpauSurfaceNV"          => "types::GLvdpauSurfaceNV",
-        "void "                     => "__gl_imports::libc::c_void",
-        "GLboolean *"               => "*mut types::GLboolean",
-        "GLchar *"                  => "*mut types::GLchar",
-        "GLcharARB *"               => "*mut types::GLcharARB",
-        "GLdouble *"                => "*mut types::GLdouble",
-        "GLenum *"                  => "*mut types::GLenum",
-        "GLfixed *"                 => "*mut types::GLfixed",
-        "GLfloat *"                 => "*mut types::GLfloat",
-        "GLhandleARB *"             => "*mut types::GLhandleARB",
-        "GLint *"                   => "*mut types::GLint",
-        "GLint64 *"                 => "*mut types::GLint64",
-        "GLint64EXT *"              => "*mut types::GLint64EXT",
-        "GLsizei *"                 => "*mut types::GLsizei",
-        "GLubyte *"                 => "*mut types::GLubyte",
-        "GLuint *"                  => "*mut types::GLuint",
-        "GLuint [2]"                => "*mut [types::GLuint; 2]",
-        "GLuint64 *"                => "*mut types::GLuint64",
-        "GLuint64EXT *"             => "*mut types::GLuint64EXT",
-        "GLushort *"                => "*mut types::GLushort",
-        "GLvoid *"                  => "*mut types::GLvoid",
-        "GLvoid **"                 => "*const *mut types::GLvoid",
-        "void *"                    => "*mut __gl_imports::libc::c_void",
-        "void **"                   => "*const *mut __gl_imports::libc::c_void",
-        "const GLboolean *"         => "*const types::GLboolean",
-        "const GLbyte *"            => "*const types::GLbyte",
-        "const GLchar *"            => "*const types::GLchar",
-        "const GLcharARB *"         => "*const types::GLcharARB",
-        "const GLclampf *"          => "*const types::GLclampf",
-        "const GLdouble *"          => "*const types::GLdouble",
-        "const GLenum *"            => "*const types::GLenum",
-        "const GLfixed *"           => "*const types::GLfixed",
-        "const GLfloat"             => "types::GLfloat",
-        "const GLfloat *"           => "*const types::GLfloat",
-        "const GLhalfNV *"          => "*const types::GLhalfNV",
-        "const GLint *"             => "*const types::GLint",
-        "const GLint64EXT *"        => "*const types::GLint64EXT",
-        "const GLintptr *"          => "*const types::GLintptr",
-        "const GLshort *"           => "*const types::GLshort",
-        "const GLsizei *"           => "*const types::GLsizei",
-        "const GLsizeiptr *"        => "*const types::GLsizeiptr",
-        "const GLubyte *"           => "*const types::GLubyte",
-        "const GLuint *"            => "*const types::GLuint",
-        "const GLuint64 *"          => "*const types::GLuint64",
-        "const GLuint64EXT *"       => "*const types::GLuint64EXT",
-        "const GLushort *"          => "*const types::GLushort",
-        "const GLvdpauSurfaceNV *"  => "*const types::GLvdpauSurfaceNV",
-        "const GLvoid *"            => "*const types::GLvoid",
-        "const void *"              => "*const __gl_imports::libc::c_void",
-        "const void **"             => "*const *const __gl_imports::libc::c_void",
-        "const void *const*"        => "*const *const __gl_imports::libc::c_void",
-        "const GLboolean **"        => "*const *const types::GLboolean",
-        "const GLchar **"           => "*const *const types::GLchar",
-        "const GLcharARB **"        => "*const *const types::GLcharARB",
-        "const GLvoid **"           => "*const *const types::GLvoid",
-        "const GLchar *const*"      => "*const *const types::GLchar",
-        "const GLvoid *const*"      => "*const *const types::GLvoid",
-        "struct _cl_context *"      => "*const types::_cl_context",
-        "struct _cl_event *"        => "*const types::_cl_event",
-
-        // glx.xml types
-        "Bool"                      => "types::Bool",
-        "Colormap"                  => "types::Colormap",
-        "DMbuffer"                  => "types::DMbuffer",
-        "Font"                      => "types::Font",
-        "GLXContext"                => "types::GLXContext",
-        "GLXContextID"              => "types::GLXContextID",
-        "GLXDrawable"               => "types::GLXDrawable",
-        "GLXFBConfig"               => "types::GLXFBConfig",
-        "GLXFBConfigSGIX"           => "types::GLXFBConfigSGIX",
-        "GLXPbuffer"                => "types::GLXPbuffer",
-        "GLXPbufferSGIX"            => "types::GLXPbufferSGIX",
-        "GLXPixmap"                 => "types::GLXPixmap",
-        "GLXVideoCaptureDeviceNV"   => "types::GLXVideoCaptureDeviceNV",
-        "GLXVideoDeviceNV"          => "types::GLXVideoDeviceNV",
-        "GLXVideoSourceSGIX"        => "types::GLXVideoSourceSGIX",
-        "GLXWindow"                 => "types::GLXWindow",
-        // "GLboolean"                 => "types::GLboolean",
-        // "GLenum"                    => "types::GLenum",
-        // "GLint"                     => "types::GLint",
-        // "GLsizei"                   => "types::GLsizei",
-        // "GLuint"                    => "types::GLuint",
-        "Pixmap"                    => "types::Pixmap",
-        //"Status"                    => "types::Status",
-        //"VLNode"                    => "types::VLNode",
-        //"VLPath"                    => "types::VLPath",
-        //"VLServer"                  => "types::VLServer",
-        "Window"                    => "types::Window",
-        "__GLXextFuncPtr"           => "types::__GLXextFuncPtr",
-        "const GLXContext"          => "const types::GLXContext",
-        "float "                    => "__gl_imports::libc::c_float",
-        "int "                      => "__gl_imports::libc::c_int",
-        "int64_t"                   => "i64",
-        "unsigned int "             => "__gl_imports::libc::c_uint",
-        "unsigned long "            => "__gl_imports::libc::c_ulong",
-        // "void "                     => "__gl_imports::libc::c_void",
-        "DMparams *"                => "*mut types::DMparams",
-        "Display *"                 => "*mut types::Display",
-        "GLXFBConfig *"             => "*mut types::GLXFBConfig",
-        "GLXFBConfigSGIX *"         => "*mut types::GLXFBConfigSGIX",
-        "GLXHyperpipeConfigSGIX *"  => "*mut types::GLXHyperpipeConfigSGIX",
-        "GLXHyperpipeNetworkSGIX *" => "*mut types::GLXHyperpipeNetworkSGIX",
-        "GLXVideoCaptureDeviceNV *" => "*mut types::GLXVideoCaptureDeviceNV",
-        "GLXVideoDeviceNV *"        => "*mut types::GLXVideoDeviceNV",
-        // "GLuint *"                  => "*mut types::GLuint",
-        "XVisualInfo *"             => "*mut types::XVisualInfo",
-        // "const GLubyte *"           => "*GLubyte",
-        "const char *"              => "*const __gl_imports::libc::c_char",
-        "const int *"               => "*const __gl_imports::libc::c_int",
-        // "const void *"              => "*const __gl_imports::libc::c_void",
-        "int *"                     => "*mut __gl_imports::libc::c_int",
-        "int32_t *"                 => "*mut i32",
-        "int64_t *"                 => "*mut i64",
-        "long *"                    => "*mut __gl_imports::libc::c_long",
-        "unsigned int *"            => "*mut __gl_imports::libc::c_uint",
-        "unsigned long *"           => "*mut __gl_imports::libc::c_ulong",
-        // "void *"                    => "*mut __gl_imports::libc::c_void",
-
-        // wgl.xml types
-        "BOOL"                      => "types::BOOL",
-        "DWORD"                     => "types::DWORD",
-        "FLOAT"                     => "types::FLOAT",
-        // "GLbitfield"                => "types::GLbitfield",
-        // "GLboolean"                 => "types::GLboolean",
-        // "GLenum"                    => "types::GLenum",
-        // "GLfloat"                   => "types::GLfloat",
-        // "GLint"                     => "types::GLint",
-        // "GLsizei"                   => "types::GLsizei",
-        // "GLuint"                    => "types::GLuint",
-        // "GLushort"                  => "types::GLushort",
-        "HANDLE"                    => "types::HANDLE",
-        "HDC"                       => "types::HDC",
-        "HENHMETAFILE"              => "types::HENHMETAFILE",
-        "HGLRC"                     => "types::HGLRC",
-        "HGPUNV"                    => "types::HGPUNV",
-        "HPBUFFERARB"               => "types::HPBUFFERARB",
-        "HPBUFFEREXT"               => "types::HPBUFFEREXT",
-        "HPVIDEODEV"                => "types::HPVIDEODEV",
-        "HVIDEOINPUTDEVICENV"       => "types::HVIDEOINPUTDEVICENV",
-        "HVIDEOOUTPUTDEVICENV"      => "types::HVIDEOOUTPUTDEVICENV",
-        "INT"                       => "types::INT",
-        "INT64"                     => "types::INT64",
-        "LPCSTR"                    => "types::LPCSTR",
-        "LPGLYPHMETRICSFLOAT"       => "types::LPGLYPHMETRICSFLOAT",
-        "LPVOID"                    => "types::LPVOID",
-        "PGPU_DEVICE"               => "types::PGPU_DEVICE",
-        "PROC"                      => "types::PROC",
-        "UINT"                      => "types::UINT",
-        "VOID"                      => "types::VOID",
-        // "int "                      => "__gl_imports::libc::c_int",
-        // "unsigned int "             => "__gl_imports::libc::c_uint",
-        // "void "                     => "__gl_imports::libc::c_void",
-        "BOOL *"                    => "*mut types::BOOL",
-        "DWORD *"                   => "*mut types::DWORD",
-        "FLOAT *"                   => "*mut types::FLOAT",
-        // "GLuint *"                  => "*mut types::GLuint",
-        "HANDLE *"                  => "*mut types::HANDLE",
-        "HGPUNV *"                  => "*mut types::HGPUNV",
-        "HPVIDEODEV *"              => "*mut types::HPVIDEODEV",
-        "HVIDEOINPUTDEVICENV *"     => "*mut types::HVIDEOINPUTDEVICENV",
-        "HVIDEOOUTPUTDEVICENV *"    => "*mut types::HVIDEOOUTPUTDEVICENV",
-        "INT32 *"                   => "*mut types::INT32",
-        "INT64 *"                   => "*mut types::INT64",
-        "UINT *"                    => "*mut types::UINT",
-        "USHORT *"                  => "*mut types::USHORT",
-        "const COLORREF *"          => "*const types::COLORREF",
-        "const DWORD *"             => "*const types::DWORD",
-        "const FLOAT *"             => "*const types::FLOAT",
-        // "const GLushort *"          => "*const types::GLushort",
-        "const HANDLE *"            => "*const types::HANDLE",
-        "const HGPUNV *"            => "*const types::HGPUNV",
-        "const LAYERPLANEDESCRIPTOR *"  => "*const types::LAYERPLANEDESCRIPTOR",
-        "const LPVOID *"            => "*const types::LPVOID",
-        "const PIXELFORMATDESCRIPTOR *" => "*const types::IXELFORMATDESCRIPTOR",
-        "const USHORT *"            => "*const types::USHORT",
-        // "const char *"              => "*const __gl_imports::libc::c_char",
-        // "const int *"               => "*const __gl_imports::libc::c_int",
-        "float *"                   => "*mut __gl_imports::libc::c_float",
-        // "int *"                     => "*mut __gl_imports::libc::c_int",
-        // "unsigned long *"           => "*mut __gl_imports::libc::c_ulong",
-        // "void *"                    => "*mut __gl_imports::libc::c_void",
-
-        // elx.xml types
-        "khronos_utime_nanoseconds_t"   => "types::khronos_utime_nanoseconds_t",
-        "khronos_uint64_t"          => "types::khronos_uint64_t",
-        "khronos_ssize_t"           => "types::khronos_ssize_t",
-        "EGLNativeDisplayType"      => "types::EGLNativeDisplayType",
-        "EGLNativePixmapType"       => "types::EGLNativePixmapType",
-        "EGLNativeWindowType"       => "types::EGLNativeWindowType",
-        "EGLint"                    => "types::EGLint",
-        "EGLint *"                  => "*mut types::EGLint",
-        "const EGLint *"            => "*const types::EGLint",
-        "NativeDisplayType"         => "types::NativeDisplayType",
-        "NativePixmapType"          => "types::NativePixmapType",
-        "NativeWindowType"          => "types::NativeWindowType",
-        //"Bool"                      => "types::Bool",
-        "EGLBoolean"                => "types::EGLBoolean",
-        "EGLenum"                   => "types::EGLenum",
-        "EGLAttribKHR"              => "types::EGLAttribKHR",
-        "EGLAttrib"                 => "types::EGLAttrib",
-        "EGLAttrib *"               => "*mut types::EGLAttrib",
-        "const EGLAttrib *"         => "*const types::EGLAttrib",
-        "EGLConfig"                 => "types::EGLConfig",
-        "EGLConfig *"               => "*mut types::EGLConfig",
-        "EGLContext"                => "types::EGLContext",
-        "EGLDeviceEXT"              => "types::EGLDeviceEXT",
-        "EGLDisplay"                => "types::EGLDisplay",
-        "EGLSurface"                => "types::EGLSurface",
-        "EGLClientBuffer"               => "types::EGLClientBuffer",
-        "__eglMustCastToProperFunctionPointerType"  => "types::__eglMustCastToProperFunctionPointerType",
-        "EGLImageKHR"               => "types::EGLImageKHR",
-        "EGLImage"                  => "types::EGLImage",
-        "EGLSyncKHR"                => "types::EGLSyncKHR",
-        "EGLSync"                   => "types::EGLSync",
-        "EGLTimeKHR"                => "types::EGLTimeKHR",
-        "EGLTime"                   => "types::EGLTime",
-        "EGLSyncNV"                 => "types::EGLSyncNV",
-        "EGLTimeNV"                 => "types::EGLTimeNV",
-        "EGLuint64NV"               => "types::EGLuint64NV",
-        "EGLStreamKHR"              => "types::EGLStreamKHR",
-        "EGLuint64KHR"              => "types::EGLuint64KHR",
-        "EGLNativeFileDescriptorKHR"    => "types::EGLNativeFileDescriptorKHR",
-        "EGLsizeiANDROID"           => "types::EGLsizeiANDROID",
-        "EGLSetBlobFuncANDROID"     => "types::EGLSetBlobFuncANDROID",
-        "EGLGetBlobFuncANDROID"     => "types::EGLGetBlobFuncANDROID",
-        "EGLClientPixmapHI"         => "types::EGLClientPixmapHI",
-
-        // failure
-        _ => panic!("Type conversion not implemented for `{}`", ty),
-    }
+/// A C type, split into its components.
+struct CType {
+    /// The base type without qualifiers, like `GLuint` or `unsigned int`.
+    base: String,
+    /// Whether the base type is `const`.
+    is_const: bool,
+    /// Whether the base type is written `struct <name>`.
+    is_struct: bool,
+    /// The number of pointer indirections.
+    ptrs: usize,
+    /// The size of the array, for declarations like `GLuint [2]`.
+    array: Option<String>,
 }
 
-pub fn build_gl_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        // Common types from OpenGL 1.1
-        "pub type GLenum = super::__gl_imports::libc::c_uint;",
-        "pub type GLboolean = super::__gl_imports::libc::c_uchar;",
-        "pub type GLbitfield = super::__gl_imports::libc::c_uint;",
-        "pub type GLvoid = super::__gl_imports::libc::c_void;",
-        "pub type GLbyte = super::__gl_imports::libc::c_char;",
-        "pub type GLshort = super::__gl_imports::libc::c_short;",
-        "pub type GLint = super::__gl_imports::libc::c_int;",
-        "pub type GLclampx = super::__gl_imports::libc::c_int;",
-        "pub type GLubyte = super::__gl_imports::libc::c_uchar;",
-        "pub type GLushort = super::__gl_imports::libc::c_ushort;",
-        "pub type GLuint = super::__gl_imports::libc::c_uint;",
-        "pub type GLsizei = super::__gl_imports::libc::c_int;",
-        "pub type GLfloat = super::__gl_imports::libc::c_float;",
-        "pub type GLclampf = super::__gl_imports::libc::c_float;",
-        "pub type GLdouble = super::__gl_imports::libc::c_double;",
-        "pub type GLclampd = super::__gl_imports::libc::c_double;",
-        "pub type GLeglImageOES = *const super::__gl_imports::libc::c_void;",
-        "pub type GLchar = super::__gl_imports::libc::c_char;",
-        "pub type GLcharARB = super::__gl_imports::libc::c_char;",
-
-        "#[cfg(target_os = \"macos\")] pub type GLhandleARB = *const super::__gl_imports::libc::c_void;",
-        "#[cfg(not(target_os = \"macos\"))] pub type GLhandleARB = super::__gl_imports::libc::c_uint;",
-
-        "pub type GLhalfARB = super::__gl_imports::libc::c_ushort;",
-        "pub type GLhalf = super::__gl_imports::libc::c_ushort;",
-
-        // Must be 32 bits
-        "pub type GLfixed = GLint;",
-
-        "pub type GLintptr = super::__gl_imports::libc::ptrdiff_t;",
-        "pub type GLsizeiptr = super::__gl_imports::libc::ptrdiff_t;",
-        "pub type GLint64 = i64;",
-        "pub type GLuint64 = u64;",
-        "pub type GLintptrARB = super::__gl_imports::libc::ptrdiff_t;",
-        "pub type GLsizeiptrARB = super::__gl_imports::libc::ptrdiff_t;",
-        "pub type GLint64EXT = i64;",
-        "pub type GLuint64EXT = u64;",
-
-        "#[repr(C)] pub struct __GLsync;",
-        "pub type GLsync = *const __GLsync;",
-
-        // compatible with OpenCL cl_context
-        "#[repr(C)] pub struct _cl_context;",
-        "#[repr(C)] pub struct _cl_event;",
-
-        "pub type GLDEBUGPROC = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);",
-        "pub type GLDEBUGPROCARB = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);",
-        "pub type GLDEBUGPROCKHR = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);",
-
-        // GLES 1 types
-        // "pub type GLclampx = i32;",
-
-        // GLES 1/2 types (tagged for GLES 1)
-        // "pub type GLbyte = i8;",
-        // "pub type GLubyte = u8;",
-        // "pub type GLfloat = GLfloat;",
-        // "pub type GLclampf = GLfloat;",
-        // "pub type GLfixed = i32;",
-        // "pub type GLint64 = i64;",
-        // "pub type GLuint64 = u64;",
-        // "pub type GLintptr = intptr_t;",
-        // "pub type GLsizeiptr = ssize_t;",
-
-        // GLES 1/2 types (tagged for GLES 2 - attribute syntax is limited)
-        // "pub type GLbyte = i8;",
-        // "pub type GLubyte = u8;",
-        // "pub type GLfloat = GLfloat;",
-        // "pub type GLclampf = GLfloat;",
-        // "pub type GLfixed = i32;",
-        // "pub type GLint64 = i64;",
-        // "pub type GLuint64 = u64;",
-        // "pub type GLint64EXT = i64;",
-        // "pub type GLuint64EXT = u64;",
-        // "pub type GLintptr = intptr_t;",
-        // "pub type GLsizeiptr = ssize_t;",
-
-        // GLES 2 types (none currently)
-
-        // Vendor extension types
-        "pub type GLDEBUGPROCAMD = extern \"system\" fn(id: GLuint, category: GLenum, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);",
-        "pub type GLhalfNV = super::__gl_imports::libc::c_ushort;",
-        "pub type GLvdpauSurfaceNV = GLintptr;",
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+impl CType {
+    fn parse(ty: &str) -> Option<CType> {
+        let ty = ty.replace("*", " * ").replace("[", " [ ").replace("]", " ] ");
+        let mut ctype = CType { base: String::new(), is_const: false, is_struct: false, ptrs: 0, array: None };
+        let mut tokens = ty.split_whitespace();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                // constness of the pointers themselves doesn't matter to Rust
+                "const" if ctype.ptrs > 0 => (),
+                "const" => ctype.is_const = true,
+                "struct" if ctype.base.is_empty() => ctype.is_struct = true,
+                "*" if !ctype.base.is_empty() => ctype.ptrs += 1,
+                "[" => {
+                    ctype.array = tokens.next().map(|s| s.to_string());
+                    if tokens.next() != Some("]") || tokens.next().is_some() {
+                        return None;
+                    }
+                }
+                word if ctype.ptrs == 0 && is_ident(word) => {
+                    if !ctype.base.is_empty() {
+                        ctype.base.push(' ');
+                    }
+                    ctype.base.push_str(word);
+                }
+                _ => return None,
+            }
+        }
+
+        if ctype.base.is_empty() { None } else { Some(ctype) }
     }
 
-    Ok(())
-}
+    /// Returns the name of the type that needs to be defined in the `types` module for this type
+    ///  to be valid, if any.
+    fn dep(&self) -> Option<&str> {
+        if primitive(&self.base).is_some() { None } else { Some(&self.base) }
+    }
 
-pub fn build_x_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        "pub type XID = super::__gl_imports::libc::c_ulong;",
-        "pub type Bool = super::__gl_imports::libc::c_int;",       // Not sure if this is correct...
-        "#[repr(C)] pub struct Display;",
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+    fn base_to_rust(&self, types_prefix: &str, libc_prefix: &str) -> String {
+        match primitive(&self.base) {
+            Some(ty) if ty.contains("::") => format!("{}{}", libc_prefix, &ty["libc::".len()..]),
+            Some(ty) => ty.to_string(),
+            None => format!("{}{}", types_prefix, self.base),
+        }
     }
 
-    Ok(())
+    /// Converts the type of a command parameter, a return type, or a parameter of a function
+    ///  pointer.
+    ///
+    /// The innermost pointer is `*const` if it points to constant data, the other ones are
+    ///  always `*const`. Arrays are passed by pointer.
+    fn to_rust_param(&self, types_prefix: &str, libc_prefix: &str) -> String {
+        let mut ty = self.base_to_rust(types_prefix, libc_prefix);
+        if let Some(ref len) = self.array {
+            return format!("*mut [{}; {}]", ty, len);
+        }
+        for i in 0 .. self.ptrs {
+            let mutability = if i == 0 && !self.is_const && !self.is_struct { "mut" } else { "const" };
+            ty = format!("*{} {}", mutability, ty);
+        }
+        ty
+    }
+
+    /// Converts the type of a typedef or of a struct field. All pointers are `*const`.
+    fn to_rust_def(&self) -> String {
+        let mut ty = self.base_to_rust("", "super::__gl_imports::libc::");
+        for _ in 0 .. self.ptrs {
+            ty = format!("*const {}", ty);
+        }
+        match self.array {
+            Some(ref len) => format!("[{}; {}]", ty, len),
+            None => ty,
+        }
+    }
 }
 
-pub fn build_glx_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        "pub type Font = XID;",
-        "pub type Pixmap = XID;",
-        "pub type Visual = ();",   // TODO: not sure
-        "pub type VisualID = super::__gl_imports::libc::c_ulong;",   // TODO: not sure
-        "pub type Window = XID;",
-        "pub type GLXFBConfigID = XID;",
-        "pub type GLXFBConfig = *const super::__gl_imports::libc::c_void;",
-        "pub type GLXContextID = XID;",
-        "pub type GLXContext = *const super::__gl_imports::libc::c_void;",
-        "pub type GLXPixmap = XID;",
-        "pub type GLXDrawable = XID;",
-        "pub type GLXWindow = XID;",
-        "pub type GLXPbuffer = XID;",
-        "pub type __GLXextFuncPtr = extern \"system\" fn();",
-        "pub type GLXVideoCaptureDeviceNV = XID;",
-        "pub type GLXVideoDeviceNV = super::__gl_imports::libc::c_int;",
-        "pub type GLXVideoSourceSGIX = XID;",
-        "pub type GLXFBConfigIDSGIX = XID;",
-        "pub type GLXFBConfigSGIX = *const super::__gl_imports::libc::c_void;",
-        "pub type GLXPbufferSGIX = XID;",
-
-        "
-            #[repr(C)]
-            pub struct XVisualInfo {
-                pub visual: *mut Visual,
-                pub visualid: VisualID,
-                pub screen: super::__gl_imports::libc::c_int,
-                pub depth: super::__gl_imports::libc::c_int,
-                pub class: super::__gl_imports::libc::c_int,
-                pub red_mask: super::__gl_imports::libc::c_ulong,
-                pub green_mask: super::__gl_imports::libc::c_ulong,
-                pub blue_mask: super::__gl_imports::libc::c_ulong,
-                pub colormap_size: super::__gl_imports::libc::c_int,
-                pub bits_per_rgb: super::__gl_imports::libc::c_int,
-            }
-        ",
-
-        "
-            #[repr(C)]
-            pub struct GLXPbufferClobberEvent {
-                pub event_type: super::__gl_imports::libc::c_int,          // GLX_DAMAGED or GLX_SAVED
-                pub draw_type: super::__gl_imports::libc::c_int,           // GLX_WINDOW or GLX_PBUFFER
-                pub serial: super::__gl_imports::libc::c_ulong,            // # of last request processed by server
-                pub send_event: Bool,           // true if this came for SendEvent request
-                pub display: *const Display,          // display the event was read from
-                pub drawable: GLXDrawable,      // XID of Drawable
-                pub buffer_mask: super::__gl_imports::libc::c_uint,        // mask indicating which buffers are affected
-                pub aux_buffer: super::__gl_imports::libc::c_uint,         // which aux buffer was affected
-                pub x: super::__gl_imports::libc::c_int,
-                pub y: super::__gl_imports::libc::c_int,
-                pub width: super::__gl_imports::libc::c_int,
-                pub height: super::__gl_imports::libc::c_int,
-                pub count: super::__gl_imports::libc::c_int,               // if nonzero, at least this many more
-            }
-        ",
-
-        "
-            #[repr(C)]
-            pub struct GLXBufferSwapComplete {
-                pub type_: super::__gl_imports::libc::c_int,
-                pub serial: super::__gl_imports::libc::c_ulong,            // # of last request processed by server
-                pub send_event: Bool,           // true if this came from a SendEvent request
-                pub display: *const Display,          // Display the event was read from
-                pub drawable: GLXDrawable,      // drawable on which event was requested in event mask
-                pub event_type: super::__gl_imports::libc::c_int,
-                pub ust: i64,
-                pub msc: i64,
-                pub sbc: i64,
-            }
-        ",
-
-        //"
-        //    typedef union __GLXEvent {
-        //        GLXPbufferClobberEvent glxpbufferclobber;
-        //        GLXBufferSwapComplete glxbufferswapcomplete;
-        //        long pad[24];
-        //    }
-        //",
-
-        "
-            #[repr(C)]
-            pub struct GLXBufferClobberEventSGIX {
-                pub type_: super::__gl_imports::libc::c_int,
-                pub serial: super::__gl_imports::libc::c_ulong,            // # of last request processed by server
-                pub send_event: Bool,           // true if this came for SendEvent request
-                pub display: *const Display,          // display the event was read from
-                pub drawable: GLXDrawable,      // i.d. of Drawable
-                pub event_type: super::__gl_imports::libc::c_int,          // GLX_DAMAGED_SGIX or GLX_SAVED_SGIX
-                pub draw_type: super::__gl_imports::libc::c_int,           // GLX_WINDOW_SGIX or GLX_PBUFFER_SGIX
-                pub mask: super::__gl_imports::libc::c_uint,               // mask indicating which buffers are affected
-                pub x: super::__gl_imports::libc::c_int,
-                pub y: super::__gl_imports::libc::c_int,
-                pub width: super::__gl_imports::libc::c_int,
-                pub height: super::__gl_imports::libc::c_int,
-                pub count: super::__gl_imports::libc::c_int,               // if nonzero, at least this many more
-            }
-        ",
+fn is_ident(word: &str) -> bool {
+    word.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
 
-        "
-            #[repr(C)]
-            pub struct GLXHyperpipeNetworkSGIX {
-                pub pipeName: [super::__gl_imports::libc::c_char; 80],   // Should be [GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX]
-                pub networkId: super::__gl_imports::libc::c_int,
-            }
-        ",
-
-        "
-            #[repr(C)]
-            pub struct GLXHyperpipeConfigSGIX {
-                pub pipeName: [super::__gl_imports::libc::c_char; 80],   // Should be [GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX]
-                pub channel: super::__gl_imports::libc::c_int,
-                pub participationType: super::__gl_imports::libc::c_uint,
-                pub timeSlice: super::__gl_imports::libc::c_int,
-            }
-        ",
-
-        "
-            #[repr(C)]
-            pub struct GLXPipeRect {
-                pub pipeName: [super::__gl_imports::libc::c_char; 80],   // Should be [GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX]
-                pub srcXOrigin: super::__gl_imports::libc::c_int,
-                pub srcYOrigin: super::__gl_imports::libc::c_int,
-                pub srcWidth: super::__gl_imports::libc::c_int,
-                pub srcHeight: super::__gl_imports::libc::c_int,
-                pub destXOrigin: super::__gl_imports::libc::c_int,
-                pub destYOrigin: super::__gl_imports::libc::c_int,
-                pub destWidth: super::__gl_imports::libc::c_int,
-                pub destHeight: super::__gl_imports::libc::c_int,
-            }
-        ",
-
-        "
-            #[repr(C)]
-            pub struct GLXPipeRectLimits {
-                pub pipeName: [super::__gl_imports::libc::c_char; 80],   // Should be [GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX]
-                pub XOrigin: super::__gl_imports::libc::c_int,
-                pub YOrigin: super::__gl_imports::libc::c_int,
-                pub maxHeight: super::__gl_imports::libc::c_int,
-                pub maxWidth: super::__gl_imports::libc::c_int,
-            }
-        "
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+/// Renames identifiers that are Rust keywords.
+fn to_rust_ident(ident: &str) -> String {
+    match ident {
+        "in" | "ref" | "type" => format!("{}_", ident),
+        ident => ident.to_string(),
     }
+}
 
-    Ok(())
+/// Converts a C type used by a command to the Rust equivalent, as seen from outside of the
+///  `types` module.
+///
+/// Returns `None` if the type can't be parsed.
+pub fn to_rust_ty(ty: &str) -> Option<String> {
+    CType::parse(ty).map(|ty| ty.to_rust_param("types::", "__gl_imports::libc::"))
 }
 
-pub fn build_win_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        // From WinNT.h
-        "pub type CHAR = super::__gl_imports::libc::c_char;",
-        "pub type HANDLE = PVOID;",
-        "pub type LONG = super::__gl_imports::libc::c_long;",
-        "pub type LPCSTR = *const super::__gl_imports::libc::c_char;",
-        "pub type VOID = super::__gl_imports::libc::c_void;",
-
-        // From Windef.h
-        "pub type BOOL = super::__gl_imports::libc::c_int;",
-        "pub type BYTE = super::__gl_imports::libc::c_uchar;",
-        "pub type COLORREF = DWORD;",
-        "pub type FLOAT = super::__gl_imports::libc::c_float;",
-        "pub type HDC = HANDLE;",
-        "pub type HENHMETAFILE = HANDLE;",
-        "pub type HGLRC = *const super::__gl_imports::libc::c_void;",
-        "pub type INT = super::__gl_imports::libc::c_int;",
-        "pub type PVOID = *const super::__gl_imports::libc::c_void;",
-        "pub type LPVOID = *const super::__gl_imports::libc::c_void;",
-        "pub type PROC = extern \"system\" fn();",     // Not sure about this one :/
-        "
-            #[repr(C)]
-            pub struct RECT {
-                left: LONG,
-                top: LONG,
-                right: LONG,
-                bottom: LONG,
-            }
-        ",
-        "pub type UINT = super::__gl_imports::libc::c_uint;",
-        "pub type USHORT = super::__gl_imports::libc::c_ushort;",
-        "pub type WORD = super::__gl_imports::libc::c_ushort;",
-
-        // From BaseTsd.h
-        "pub type INT32 = i32;",
-        "pub type INT64 = i64;",
-
-        // From IntSafe.h
-        "pub type DWORD = super::__gl_imports::libc::c_ulong;",
-
-        // From Wingdi.h
-        "
-            #[repr(C)]
-            pub struct POINTFLOAT {
-                pub x: FLOAT,
-                pub y: FLOAT,
-            }
-        ",
-        "
-            #[repr(C)]
-            pub struct GLYPHMETRICSFLOAT {
-                pub gmfBlackBoxX: FLOAT,
-                pub gmfBlackBoxY: FLOAT,
-                pub gmfptGlyphOrigin: POINTFLOAT,
-                pub gmfCellIncX: FLOAT,
-                pub gmfCellIncY: FLOAT,
-            }
-        ",
-        "pub type LPGLYPHMETRICSFLOAT = *const GLYPHMETRICSFLOAT;",
-        "
-            #[repr(C)]
-            pub struct LAYERPLANEDESCRIPTOR {
-                pub nSize: WORD,
-                pub nVersion: WORD,
-                pub dwFlags: DWORD,
-                pub iPixelType: BYTE,
-                pub cColorBits: BYTE,
-                pub cRedBits: BYTE,
-                pub cRedShift: BYTE,
-                pub cGreenBits: BYTE,
-                pub cGreenShift: BYTE,
-                pub cBlueBits: BYTE,
-                pub cBlueShift: BYTE,
-                pub cAlphaBits: BYTE,
-                pub cAlphaShift: BYTE,
-                pub cAccumBits: BYTE,
-                pub cAccumRedBits: BYTE,
-                pub cAccumGreenBits: BYTE,
-                pub cAccumBlueBits: BYTE,
-                pub cAccumAlphaBits: BYTE,
-                pub cDepthBits: BYTE,
-                pub cStencilBits: BYTE,
-                pub cAuxBuffers: BYTE,
-                pub iLayerType: BYTE,
-                pub bReserved: BYTE,
-                pub crTransparent: COLORREF,
-            }
-        ",
-        "
-            #[repr(C)]
-            pub struct PIXELFORMATDESCRIPTOR {
-                pub nSize: WORD,
-                pub nVersion: WORD,
-                pub dwFlags: DWORD,
-                pub iPixelType: BYTE,
-                pub cColorBits: BYTE,
-                pub cRedBits: BYTE,
-                pub cRedShift: BYTE,
-                pub cGreenBits: BYTE,
-                pub cGreenShift: BYTE,
-                pub cBlueBits: BYTE,
-                pub cBlueShift: BYTE,
-                pub cAlphaBits: BYTE,
-                pub cAlphaShift: BYTE,
-                pub cAccumBits: BYTE,
-                pub cAccumRedBits: BYTE,
-                pub cAccumGreenBits: BYTE,
-                pub cAccumBlueBits: BYTE,
-                pub cAccumAlphaBits: BYTE,
-                pub cDepthBits: BYTE,
-                pub cStencilBits: BYTE,
-                pub cAuxBuffers: BYTE,
-                pub iLayerType: BYTE,
-                pub bReserved: BYTE,
-                pub dwLayerMask: DWORD,
-                pub dwVisibleMask: DWORD,
-                pub dwDamageMask: DWORD,
-            }
-        "
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+/// Returns the name of the type from the `types` module that a C type refers to, if any.
+pub fn type_dep(ty: &str) -> Option<String> {
+    CType::parse(ty).and_then(|ty| ty.dep().map(|s| s.to_string()))
+}
+
+/// Returns the Rust definition of a type along with the names of the types it depends on,
+///  or `None` if the type is unknown.
+///
+/// `is_struct` must be true if the type has been referred to as `struct <name>`, in which case
+///  an opaque struct is generated if the registry does not define it.
+pub fn gen_type_def(registry: &Registry, name: &str, is_struct: bool)
+                    -> Option<(String, Vec<(String, bool)>)>
+{
+    fn builtin(table: &[Builtin], name: &str) -> Option<(String, Vec<(String, bool)>)> {
+        table.iter().find(|b| b.name == name).map(|b| {
+            (b.def.to_string(), b.deps.iter().map(|d| (d.to_string(), false)).collect())
+        })
     }
 
-    Ok(())
-}
+    if let Some(def) = builtin(OVERRIDES, name) {
+        return Some(def);
+    }
+
+    let ty = registry.get_type(name);
 
-pub fn build_wgl_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        // From WinNT.h,
-        // #define DECLARE_HANDLE(name) struct name##__{int unused;}; typedef struct name##__ *name
-        "pub type HPBUFFERARB = *const super::__gl_imports::libc::c_void;",
-        "pub type HPBUFFEREXT = *const super::__gl_imports::libc::c_void;",
-        "pub type HVIDEOOUTPUTDEVICENV = *const super::__gl_imports::libc::c_void;",
-        "pub type HPVIDEODEV = *const super::__gl_imports::libc::c_void;",
-        "pub type HPGPUNV = *const super::__gl_imports::libc::c_void;",
-        "pub type HGPUNV = *const super::__gl_imports::libc::c_void;",
-        "pub type HVIDEOINPUTDEVICENV = *const super::__gl_imports::libc::c_void;",
-
-        "
-            #[repr(C)]
-            pub struct _GPU_DEVICE {
-                cb: DWORD,
-                DeviceName: [CHAR; 32],
-                DeviceString: [CHAR; 128],
-                Flags: DWORD,
-                rcVirtualScreen: RECT,
+    // definitions from the registry
+    if let Some(ty) = ty {
+        let mut deps = Vec::new();
+        // `requires` often names a header rather than a type, so it is only a dependency if it
+        //  can actually be defined
+        if let Some(ref requires) = ty.requires {
+            if gen_type_def(registry, requires, false).is_some() {
+                deps.push((requires.clone(), false));
             }
-        ",
+        }
+        {
+            let mut convert = |cty: &str| -> Option<CType> {
+                let cty = CType::parse(cty);
+                if let Some(ref cty) = cty {
+                    if let Some(dep) = cty.dep() {
+                        deps.push((dep.to_string(), cty.is_struct));
+                    }
+                }
+                cty
+            };
+
+            let def = match ty.def {
+                TypeDef::Alias(ref cty) => {
+                    convert(cty).map(|cty| format!("pub type {} = {};", name, cty.to_rust_def()))
+                }
+                TypeDef::FnPtr { ref ret, ref params } => {
+                    let params: Option<Vec<String>> = params.iter().map(|p| {
+                        convert(&p.ty).map(|cty| {
+                            let cty = cty.to_rust_param("", "super::__gl_imports::libc::");
+                            if p.ident.is_empty() {
+                                cty
+                            } else {
+                                format!("{}: {}", to_rust_ident(&p.ident), cty)
+                            }
+                        })
+                    }).collect();
+                    let ret = convert(ret).map(|cty| {
+                        if cty.base == "void" && cty.ptrs == 0 {
+                            String::new()
+                        } else {
+                            format!(" -> {}", cty.to_rust_param("", "super::__gl_imports::libc::"))
+                        }
+                    });
+                    match (params, ret) {
+                        (Some(params), Some(ret)) => Some(format!(
+                            "pub type {} = extern \"system\" fn({}){};", name, params.join(", "), ret)),
+                        _ => None,
+                    }
+                }
+                TypeDef::Struct(ref fields) => {
+                    let fields: Option<Vec<String>> = fields.iter().map(|f| {
+                        convert(&f.ty).map(|cty| {
+                            format!("    pub {}: {},", to_rust_ident(&f.ident), cty.to_rust_def())
+                        })
+                    }).collect();
+                    fields.map(|fields| {
+                        format!("#[repr(C)]\npub struct {} {{\n{}\n}}", name, fields.join("\n"))
+                    })
+                }
+                TypeDef::Opaque => Some(format!("#[repr(C)] pub struct {};", name)),
+                TypeDef::Handle => {
+                    Some(format!("pub type {} = *const super::__gl_imports::libc::c_void;", name))
+                }
+                TypeDef::External | TypeDef::Unsupported(_) => None,
+            };
+
+            if let Some(def) = def {
+                return Some((def, deps));
+            }
+        }
+    }
 
-        "pub struct GPU_DEVICE(_GPU_DEVICE);",
-        "pub struct PGPU_DEVICE(*const _GPU_DEVICE);",
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+    if let Some(def) = builtin(PLATFORM, name) {
+        return Some(def);
     }
 
-    Ok(())
+    match ty {
+        // the values of the placeholders must be defined by the user, next to the bindings
+        Some(&Type { def: TypeDef::External, .. }) => {
+            Some((format!("pub type {} = super::{};", name, name), Vec::new()))
+        }
+        _ if is_struct => Some((format!("#[repr(C)] pub struct {};", name), Vec::new())),
+        _ => None,
+    }
 }
 
-pub fn build_egl_aliases<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
-    for l in [
-        // platform-specific aliases are unknown
-        // IMPORTANT: these are alises to the same level of the bindings
-        // the values must be defined by the user
-        "pub type khronos_utime_nanoseconds_t = super::khronos_utime_nanoseconds_t;",
-        "pub type khronos_uint64_t = super::khronos_uint64_t;",
-        "pub type khronos_ssize_t = super::khronos_ssize_t;",
-        "pub type EGLNativeDisplayType = super::EGLNativeDisplayType;",
-        "pub type EGLNativePixmapType = super::EGLNativePixmapType;",
-        "pub type EGLNativeWindowType = super::EGLNativeWindowType;",
-        "pub type EGLint = super::EGLint;",
-        "pub type NativeDisplayType = super::NativeDisplayType;",
-        "pub type NativePixmapType = super::NativePixmapType;",
-        "pub type NativeWindowType = super::NativeWindowType;",
-
-        // EGL alises
-        "pub type Bool = EGLBoolean;",  // TODO: not sure
-        "pub type EGLBoolean = super::__gl_imports::libc::c_uint;",
-        "pub type EGLenum = super::__gl_imports::libc::c_uint;",
-        "pub type EGLAttribKHR = super::__gl_imports::libc::intptr_t;",
-        "pub type EGLAttrib = super::__gl_imports::libc::intptr_t;",
-        "pub type EGLConfig = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLContext = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLDeviceEXT = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLDisplay = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLSurface = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLClientBuffer = *const super::__gl_imports::libc::c_void;",
-        "pub type __eglMustCastToProperFunctionPointerType = extern \"system\" fn() -> ();",
-        "pub type EGLImageKHR = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLImage = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLSyncKHR = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLSync = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLTimeKHR = khronos_utime_nanoseconds_t;",
-        "pub type EGLTime = khronos_utime_nanoseconds_t;",
-        "pub type EGLSyncNV = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLTimeNV = khronos_utime_nanoseconds_t;",
-        "pub type EGLuint64NV = khronos_utime_nanoseconds_t;",
-        "pub type EGLStreamKHR = *const super::__gl_imports::libc::c_void;",
-        "pub type EGLuint64KHR = khronos_uint64_t;",
-        "pub type EGLNativeFileDescriptorKHR = super::__gl_imports::libc::c_int;",
-        "pub type EGLsizeiANDROID = khronos_ssize_t;",
-        "pub type EGLSetBlobFuncANDROID = extern \"system\" fn(*const super::__gl_imports::libc::c_void, EGLsizeiANDROID, *const super::__gl_imports::libc::c_void, EGLsizeiANDROID) -> ();",
-        "pub type EGLGetBlobFuncANDROID = extern \"system\" fn(*const super::__gl_imports::libc::c_void, EGLsizeiANDROID, *mut super::__gl_imports::libc::c_void, EGLsizeiANDROID) -> EGLsizeiANDROID;",
-
-        "
-            #[repr(C)]
-            pub struct EGLClientPixmapHI {
-                pData: *const super::__gl_imports::libc::c_void,
-                iWidth: EGLint,
-                iHeight: EGLint,
-                iStride: EGLint,
-            }
-        "
-    ].iter() {
-        try!(writeln!(dest, "{}", l));
+/// Returns true if zero is a valid value of a C type, because it is an integer, a float or a raw
+///  pointer. It isn't for function pointers, structs, and the types that are defined next to the
+///  bindings.
+pub fn is_zeroable(registry: &Registry, ty: &str) -> bool {
+    let cty = match CType::parse(ty) {
+        Some(cty) => cty,
+        None => return false,
+    };
+    // `EGLint` is defined next to the bindings, but eglplatform.h makes it a 32-bit integer
+    if cty.ptrs > 0 || primitive(&cty.base).is_some() || cty.base == "EGLint" {
+        return true;
     }
 
-    Ok(())
+    // the Rust definition is one or more `pub type <name> = <ty>;` for the aliases, and each of
+    //  them must be zeroable
+    let def = match gen_type_def(registry, &cty.base, cty.is_struct) {
+        Some((def, _)) => def,
+        None => return false,
+    };
+    let aliases: Vec<&str> = def.split("pub type ").skip(1).filter_map(|alias| {
+        let start = alias.find(" = ").map(|i| i + 3);
+        let end = alias.find(';');
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => Some(alias[start .. end].trim()),
+            _ => None,
+        }
+    }).collect();
+    !aliases.is_empty() && aliases.iter().all(|&rust_ty| {
+        if rust_ty.starts_with("*") || rust_ty.starts_with("super::__gl_imports::libc::") {
+            true
+        } else if rust_ty.starts_with("extern ") || rust_ty.starts_with("super::") {
+            false
+        } else {
+            match rust_ty {
+                "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "f32" | "f64" => true,
+                // another type of the `types` module
+                name => is_ident(name) && is_zeroable(registry, name),
+            }
+        }
+    })
+}
+
+/// Returns the Rust equivalent of a primitive C type, with `libc::` standing for the path to the
+///  `libc` crate.
+fn primitive(ty: &str) -> Option<&'static str> {
+    let ty = match ty {
+        "void"                          => "libc::c_void",
+        "char"                          => "libc::c_char",
+        "signed char"                   => "libc::c_schar",
+        "unsigned char"                 => "libc::c_uchar",
+        "short"                         => "libc::c_short",
+        "unsigned short"                => "libc::c_ushort",
+        "int"                           => "libc::c_int",
+        "unsigned int"                  => "libc::c_uint",
+        "long"                          => "libc::c_long",
+        "unsigned long"                 => "libc::c_ulong",
+        "long long"                     => "libc::c_longlong",
+        "unsigned long long"            => "libc::c_ulonglong",
+        "float"                         => "libc::c_float",
+        "double"                        => "libc::c_double",
+
+        // stddef.h and inttypes.h
+        "int8_t"                        => "i8",
+        "uint8_t"                       => "u8",
+        "int16_t"                       => "i16",
+        "uint16_t"                      => "u16",
+        "int32_t"                       => "i32",
+        "uint32_t"                      => "u32",
+        "int64_t"                       => "i64",
+        "uint64_t"                      => "u64",
+        "intptr_t"                      => "libc::intptr_t",
+        "ptrdiff_t"                     => "libc::ptrdiff_t",
+        "ssize_t"                       => "libc::ssize_t",
+        "size_t"                        => "libc::size_t",
+
+        // KHR/khrplatform.h
+        "khronos_int8_t"                => "i8",
+        "khronos_uint8_t"               => "u8",
+        "khronos_int16_t"               => "i16",
+        "khronos_uint16_t"              => "u16",
+        "khronos_int32_t"               => "i32",
+        "khronos_uint32_t"              => "u32",
+        "khronos_int64_t"               => "i64",
+        "khronos_uint64_t"              => "u64",
+        "khronos_float_t"               => "libc::c_float",
+        "khronos_intptr_t"              => "libc::intptr_t",
+        "khronos_ssize_t"               => "libc::ssize_t",
+        "khronos_utime_nanoseconds_t"   => "u64",
+
+        _ => return None,
+    };
+
+    Some(ty)
 }
+
+/// Definitions that take precedence over the registry.
+static OVERRIDES: &'static [Builtin] = &[
+    // defined with `#ifdef __APPLE__` in the registry
+    Builtin { name: "GLhandleARB", deps: &[], def: "\
+        #[cfg(target_os = \"macos\")] pub type GLhandleARB = *const super::__gl_imports::libc::c_void;\n\
+        #[cfg(not(target_os = \"macos\"))] pub type GLhandleARB = super::__gl_imports::libc::c_uint;" },
+
+    // these keep the signature of the callbacks that users have already written
+    Builtin { name: "GLDEBUGPROC", deps: &["GLenum", "GLuint", "GLsizei", "GLchar"],
+              def: "pub type GLDEBUGPROC = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);" },
+    Builtin { name: "GLDEBUGPROCARB", deps: &["GLenum", "GLuint", "GLsizei", "GLchar"],
+              def: "pub type GLDEBUGPROCARB = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);" },
+    Builtin { name: "GLDEBUGPROCKHR", deps: &["GLenum", "GLuint", "GLsizei", "GLchar"],
+              def: "pub type GLDEBUGPROCKHR = extern \"system\" fn(source: GLenum, gltype: GLenum, id: GLuint, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);" },
+    Builtin { name: "GLDEBUGPROCAMD", deps: &["GLenum", "GLuint", "GLsizei", "GLchar"],
+              def: "pub type GLDEBUGPROCAMD = extern \"system\" fn(id: GLuint, category: GLenum, severity: GLenum, length: GLsizei, message: *const GLchar, userParam: *mut super::__gl_imports::libc::c_void);" },
+];
+
+/// Definitions of the types that the registries use without defining them.
+static PLATFORM: &'static [Builtin] = &[
+    // GL types used by glx.xml, wgl.xml, and by the enums of egl.xml
+    Builtin { name: "GLenum", deps: &[], def: "pub type GLenum = super::__gl_imports::libc::c_uint;" },
+    Builtin { name: "GLboolean", deps: &[], def: "pub type GLboolean = super::__gl_imports::libc::c_uchar;" },
+    Builtin { name: "GLbitfield", deps: &[], def: "pub type GLbitfield = super::__gl_imports::libc::c_uint;" },
+    Builtin { name: "GLint", deps: &[], def: "pub type GLint = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "GLuint", deps: &[], def: "pub type GLuint = super::__gl_imports::libc::c_uint;" },
+    Builtin { name: "GLsizei", deps: &[], def: "pub type GLsizei = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "GLfloat", deps: &[], def: "pub type GLfloat = super::__gl_imports::libc::c_float;" },
+    Builtin { name: "GLubyte", deps: &[], def: "pub type GLubyte = super::__gl_imports::libc::c_uchar;" },
+    Builtin { name: "GLushort", deps: &[], def: "pub type GLushort = super::__gl_imports::libc::c_ushort;" },
+    Builtin { name: "GLchar", deps: &[], def: "pub type GLchar = super::__gl_imports::libc::c_char;" },
+    Builtin { name: "GLintptr", deps: &[], def: "pub type GLintptr = super::__gl_imports::libc::ptrdiff_t;" },
+    Builtin { name: "GLsizeiptr", deps: &[], def: "pub type GLsizeiptr = super::__gl_imports::libc::ptrdiff_t;" },
+    Builtin { name: "GLuint64", deps: &[], def: "pub type GLuint64 = u64;" },
+
+    // From Xlib.h
+    Builtin { name: "XID", deps: &[], def: "pub type XID = super::__gl_imports::libc::c_ulong;" },
+    Builtin { name: "Bool", deps: &[], def: "pub type Bool = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "Status", deps: &[], def: "pub type Status = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "Display", deps: &[], def: "#[repr(C)] pub struct Display;" },
+    Builtin { name: "Screen", deps: &[], def: "#[repr(C)] pub struct Screen;" },
+    Builtin { name: "Colormap", deps: &["XID"], def: "pub type Colormap = XID;" },
+    Builtin { name: "Font", deps: &["XID"], def: "pub type Font = XID;" },
+    Builtin { name: "Pixmap", deps: &["XID"], def: "pub type Pixmap = XID;" },
+    Builtin { name: "Window", deps: &["XID"], def: "pub type Window = XID;" },
+    Builtin { name: "Visual", deps: &[], def: "pub type Visual = ();" },   // TODO: not sure
+    Builtin { name: "VisualID", deps: &[], def: "pub type VisualID = super::__gl_imports::libc::c_ulong;" },
+    Builtin { name: "XVisualInfo", deps: &["Visual", "VisualID"], def: "\
+        #[repr(C)]\n\
+        pub struct XVisualInfo {\n\
+        \x20   pub visual: *mut Visual,\n\
+        \x20   pub visualid: VisualID,\n\
+        \x20   pub screen: super::__gl_imports::libc::c_int,\n\
+        \x20   pub depth: super::__gl_imports::libc::c_int,\n\
+        \x20   pub class: super::__gl_imports::libc::c_int,\n\
+        \x20   pub red_mask: super::__gl_imports::libc::c_ulong,\n\
+        \x20   pub green_mask: super::__gl_imports::libc::c_ulong,\n\
+        \x20   pub blue_mask: super::__gl_imports::libc::c_ulong,\n\
+        \x20   pub colormap_size: super::__gl_imports::libc::c_int,\n\
+        \x20   pub bits_per_rgb: super::__gl_imports::libc::c_int,\n\
+        }" },
+
+    // From SGI's dmedia and Video Library headers
+    Builtin { name: "DMbuffer", deps: &[], def: "pub type DMbuffer = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "DMparams", deps: &[], def: "#[repr(C)] pub struct DMparams;" },
+    Builtin { name: "VLNode", deps: &[], def: "pub type VLNode = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "VLPath", deps: &[], def: "pub type VLPath = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "VLServer", deps: &[], def: "pub type VLServer = *const super::__gl_imports::libc::c_void;" },
+
+    // From WinNT.h
+    Builtin { name: "CHAR", deps: &[], def: "pub type CHAR = super::__gl_imports::libc::c_char;" },
+    Builtin { name: "HANDLE", deps: &["PVOID"], def: "pub type HANDLE = PVOID;" },
+    Builtin { name: "LONG", deps: &[], def: "pub type LONG = super::__gl_imports::libc::c_long;" },
+    Builtin { name: "LPCSTR", deps: &[], def: "pub type LPCSTR = *const super::__gl_imports::libc::c_char;" },
+    Builtin { name: "VOID", deps: &[], def: "pub type VOID = super::__gl_imports::libc::c_void;" },
+
+    // From Windef.h
+    Builtin { name: "BOOL", deps: &[], def: "pub type BOOL = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "BYTE", deps: &[], def: "pub type BYTE = super::__gl_imports::libc::c_uchar;" },
+    Builtin { name: "COLORREF", deps: &["DWORD"], def: "pub type COLORREF = DWORD;" },
+    Builtin { name: "FLOAT", deps: &[], def: "pub type FLOAT = super::__gl_imports::libc::c_float;" },
+    Builtin { name: "HDC", deps: &["HANDLE"], def: "pub type HDC = HANDLE;" },
+    Builtin { name: "HENHMETAFILE", deps: &["HANDLE"], def: "pub type HENHMETAFILE = HANDLE;" },
+    Builtin { name: "HGLRC", deps: &[], def: "pub type HGLRC = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "INT", deps: &[], def: "pub type INT = super::__gl_imports::libc::c_int;" },
+    Builtin { name: "PVOID", deps: &[], def: "pub type PVOID = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "LPVOID", deps: &[], def: "pub type LPVOID = *const super::__gl_imports::libc::c_void;" },
+    Builtin { name: "PROC", deps: &[], def: "pub type PROC = extern \"system\" fn();" },     // Not sure about this one :/
+    Builtin { name: "RECT", deps: &["LONG"], def: "\
+        #[repr(C)]\n\
+        pub struct RECT {\n\
+        \x20   pub left: LONG,\n\
+        \x20   pub top: LONG,\n\
+        \x20   pub right: LONG,\n\
+        \x20   pub bottom: LONG,\n\
+        }" },
+    Builtin { name: "UINT", deps: &[], def: "pub type UINT = super::__gl_imports::libc::c_uint;" },
+    Builtin { name: "USHORT", deps: &[], def: "pub type USHORT = super::__gl_imports::libc::c_ushort;" },
+    Builtin { name: "WORD", deps: &[], def: "pub type WORD = super::__gl_imports::libc::c_ushort;" },
+
+    // From BaseTsd.h
+    Builtin { name: "INT32", deps: &[], def: "pub type INT32 = i32;" },
+    Builtin { name: "INT64", deps: &[], def: "pub type INT64 = i64;" },
+
+    // From IntSafe.h
+    Builtin { name: "DWORD", deps: &[], def: "pub type DWORD = super::__gl_imports::libc::c_ulong;" },
+
+    // From Wingdi.h
+    Builtin { name: "POINTFLOAT", deps: &["FLOAT"], def: "\
+        #[repr(C)]\n\
+        pub struct POINTFLOAT {\n\
+        \x20   pub x: FLOAT,\n\
+        \x20   pub y: FLOAT,\n\
+        }" },
+    Builtin { name: "GLYPHMETRICSFLOAT", deps: &["FLOAT", "POINTFLOAT"], def: "\
+        #[repr(C)]\n\
+        pub struct GLYPHMETRICSFLOAT {\n\
+        \x20   pub gmfBlackBoxX: FLOAT,\n\
+        \x20   pub gmfBlackBoxY: FLOAT,\n\
+        \x20   pub gmfptGlyphOrigin: POINTFLOAT,\n\
+        \x20   pub gmfCellIncX: FLOAT,\n\
+        \x20   pub gmfCellIncY: FLOAT,\n\
+        }" },
+    Builtin { name: "LPGLYPHMETRICSFLOAT", deps: &["GLYPHMETRICSFLOAT"],
+              def: "pub type LPGLYPHMETRICSFLOAT = *const GLYPHMETRICSFLOAT;" },
+    Builtin { name: "LAYERPLANEDESCRIPTOR", deps: &["WORD", "DWORD", "BYTE", "COLORREF"], def: "\
+        #[repr(C)]\n\
+        pub struct LAYERPLANEDESCRIPTOR {\n\
+        \x20   pub nSize: WORD,\n\
+        \x20   pub nVersion: WORD,\n\
+        \x20   pub dwFlags: DWORD,\n\
+        \x20   pub iPixelType: BYTE,\n\
+        \x20   pub cColorBits: BYTE,\n\
+        \x20   pub cRedBits: BYTE,\n\
+        \x20   pub cRedShift: BYTE,\n\
+        \x20   pub cGreenBits: BYTE,\n\
+        \x20   pub cGreenShift: BYTE,\n\
+        \x20   pub cBlueBits: BYTE,\n\
+        \x20   pub cBlueShift: BYTE,\n\
+        \x20   pub cAlphaBits: BYTE,\n\
+        \x20   pub cAlphaShift: BYTE,\n\
+        \x20   pub cAccumBits: BYTE,\n\
+        \x20   pub cAccumRedBits: BYTE,\n\
+        \x20   pub cAccumGreenBits: BYTE,\n\
+        \x20   pub cAccumBlueBits: BYTE,\n\
+        \x20   pub cAccumAlphaBits: BYTE,\n\
+        \x20   pub cDepthBits: BYTE,\n\
+        \x20   pub cStencilBits: BYTE,\n\
+        \x20   pub cAuxBuffers: BYTE,\n\
+        \x20   pub iLayerType: BYTE,\n\
+        \x20   pub bReserved: BYTE,\n\
+        \x20   pub crTransparent: COLORREF,\n\
+        }" },
+    Builtin { name: "PIXELFORMATDESCRIPTOR", deps: &["WORD", "DWORD", "BYTE"], def: "\
+        #[repr(C)]\n\
+        pub struct PIXELFORMATDESCRIPTOR {\n\
+        \x20   pub nSize: WORD,\n\
+        \x20   pub nVersion: WORD,\n\
+        \x20   pub dwFlags: DWORD,\n\
+        \x20   pub iPixelType: BYTE,\n\
+        \x20   pub cColorBits: BYTE,\n\
+        \x20   pub cRedBits: BYTE,\n\
+        \x20   pub cRedShift: BYTE,\n\
+        \x20   pub cGreenBits: BYTE,\n\
+        \x20   pub cGreenShift: BYTE,\n\
+        \x20   pub cBlueBits: BYTE,\n\
+        \x20   pub cBlueShift: BYTE,\n\
+        \x20   pub cAlphaBits: BYTE,\n\
+        \x20   pub cAlphaShift: BYTE,\n\
+        \x20   pub cAccumBits: BYTE,\n\
+        \x20   pub cAccumRedBits: BYTE,\n\
+        \x20   pub cAccumGreenBits: BYTE,\n\
+        \x20   pub cAccumBlueBits: BYTE,\n\
+        \x20   pub cAccumAlphaBits: BYTE,\n\
+        \x20   pub cDepthBits: BYTE,\n\
+        \x20   pub cStencilBits: BYTE,\n\
+        \x20   pub cAuxBuffers: BYTE,\n\
+        \x20   pub iLayerType: BYTE,\n\
+        \x20   pub bReserved: BYTE,\n\
+        \x20   pub dwLayerMask: DWORD,\n\
+        \x20   pub dwVisibleMask: DWORD,\n\
+        \x20   pub dwDamageMask: DWORD,\n\
+        }" },
+];
@@ -0,0 +1,535 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of a `Registry` to and from JSON.
+//!
+//! The document is an object with one member per field of `Registry`, plus a `schema` member
+//!  holding `JSON_SCHEMA_VERSION`. Structs become objects whose members are named after their
+//!  fields, `None` becomes `null`, versions are written as `"major.minor"` strings and `len`
+//!  attributes as they appear in the XML. A `TypeDef` is an object whose `kind` member is one of `external`,
+//!  `alias` (with `ty`), `fn_ptr` (with `ret` and `params`), `struct` (with `fields`), `opaque`,
+//!  `handle` or `unsupported` (with `code`).
+
+extern crate rustc_serialize;
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+use self::rustc_serialize::json::{Json, ParserError, ToJson};
+
+use registry::{Binding, Cmd, Enum, EnumNs, EquivalentEnum, Extension, Feature, GlxOpcode, Group};
+use registry::{Len, Provenance, Registry, Remove, Require, Type, TypeDef, Version};
+use Error;
+
+/// The version of the schema written by `Registry::write_json`. It changes whenever the schema
+///  changes in a way that older readers can't handle.
+pub const JSON_SCHEMA_VERSION: u64 = 2;
+
+macro_rules! object {
+    ($($key:expr => $value:expr),*) => ({
+        let mut obj = BTreeMap::new();
+        $(obj.insert($key.to_string(), $value.to_json());)*
+        Json::Object(obj)
+    })
+}
+
+impl Registry {
+    /// Writes the registry as JSON. Commands, enums and types keep the order of the registry.
+    pub fn write_json<W>(&self, dest: &mut W) -> io::Result<()> where W: io::Write {
+        writeln!(dest, "{}", self.to_json().pretty())
+    }
+
+    /// Reads a registry written by `write_json`.
+    pub fn from_json<R: io::Read>(mut data: R) -> Result<Registry, Error> {
+        let json = match Json::from_reader(&mut data) {
+            Ok(json) => json,
+            Err(ParserError::SyntaxError(code, line, col)) => return Err(Error::Json {
+                path: String::new(),
+                msg: format!("{} at {}:{}", rustc_serialize::json::error_str(code), line, col),
+            }),
+            Err(ParserError::IoError(err)) => return Err(Error::Io(err)),
+        };
+        read_registry(&Value { json: &json, path: String::new() })
+    }
+}
+
+impl ToJson for Registry {
+    fn to_json(&self) -> Json {
+        object! {
+            "schema" => JSON_SCHEMA_VERSION,
+            "groups" => self.groups,
+            "types" => self.types,
+            "enum_nss" => self.enum_nss,
+            "enums" => self.enums,
+            "equivalent_enums" => self.equivalent_enums,
+            "cmds" => self.cmds,
+            "features" => self.features,
+            "extensions" => self.extensions,
+            "aliases" => self.aliases,
+            "provenance" => self.provenance
+        }
+    }
+}
+
+impl ToJson for Version {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+}
+
+impl ToJson for Provenance {
+    fn to_json(&self) -> Json {
+        object! {
+            "introduced_in" => self.introduced_in,
+            "removed_in" => self.removed_in,
+            "removed_from_core" => self.removed_from_core
+        }
+    }
+}
+
+impl ToJson for Group {
+    fn to_json(&self) -> Json {
+        object! {
+            "name" => self.name,
+            "enums" => self.enums
+        }
+    }
+}
+
+impl ToJson for Type {
+    fn to_json(&self) -> Json {
+        object! {
+            "name" => self.name,
+            "api" => self.api,
+            "requires" => self.requires,
+            "comment" => self.comment,
+            "def" => self.def
+        }
+    }
+}
+
+impl ToJson for TypeDef {
+    fn to_json(&self) -> Json {
+        match *self {
+            TypeDef::External => object! { "kind" => "external" },
+            TypeDef::Alias(ref ty) => object! { "kind" => "alias", "ty" => ty },
+            TypeDef::FnPtr { ref ret, ref params } =>
+                object! { "kind" => "fn_ptr", "ret" => ret, "params" => params },
+            TypeDef::Struct(ref fields) => object! { "kind" => "struct", "fields" => fields },
+            TypeDef::Opaque => object! { "kind" => "opaque" },
+            TypeDef::Handle => object! { "kind" => "handle" },
+            TypeDef::Unsupported(ref code) => object! { "kind" => "unsupported", "code" => code },
+        }
+    }
+}
+
+impl ToJson for EnumNs {
+    fn to_json(&self) -> Json {
+        object! {
+            "namespace" => self.namespace,
+            "group" => self.group,
+            "ty" => self.ty,
+            "start" => self.start,
+            "end" => self.end,
+            "vendor" => self.vendor,
+            "comment" => self.comment
+        }
+    }
+}
+
+impl ToJson for Enum {
+    fn to_json(&self) -> Json {
+        object! {
+            "ident" => self.ident,
+            "value" => self.value,
+            "alias" => self.alias,
+            "ty" => self.ty,
+            "api" => self.api,
+            "block" => self.block
+        }
+    }
+}
+
+/// Written as the enum, with an extra `equivalent_to` member.
+impl ToJson for EquivalentEnum {
+    fn to_json(&self) -> Json {
+        let mut json = self.enm.to_json();
+        if let Json::Object(ref mut obj) = json {
+            obj.insert("equivalent_to".to_string(), self.equivalent_to.to_json());
+        }
+        json
+    }
+}
+
+impl ToJson for Binding {
+    fn to_json(&self) -> Json {
+        object! {
+            "ident" => self.ident,
+            "ty" => self.ty,
+            "group" => self.group,
+            "len" => self.len.as_ref().map(|len| len.to_string()),
+            "class" => self.class,
+            "kind" => self.kind
+        }
+    }
+}
+
+impl ToJson for Cmd {
+    fn to_json(&self) -> Json {
+        object! {
+            "proto" => self.proto,
+            "params" => self.params,
+            "is_safe" => self.is_safe,
+            "alias" => self.alias,
+            "vecequiv" => self.vecequiv,
+            "glx" => self.glx
+        }
+    }
+}
+
+impl ToJson for GlxOpcode {
+    fn to_json(&self) -> Json {
+        object! {
+            "ty" => self.ty,
+            "opcode" => self.opcode,
+            "name" => self.name,
+            "comment" => self.comment
+        }
+    }
+}
+
+impl ToJson for Feature {
+    fn to_json(&self) -> Json {
+        object! {
+            "api" => self.api,
+            "name" => self.name,
+            "number" => self.number,
+            "requires" => self.requires,
+            "removes" => self.removes
+        }
+    }
+}
+
+impl ToJson for Require {
+    fn to_json(&self) -> Json {
+        object! {
+            "api" => self.api,
+            "profile" => self.profile,
+            "comment" => self.comment,
+            "enums" => self.enums,
+            "commands" => self.commands
+        }
+    }
+}
+
+impl ToJson for Remove {
+    fn to_json(&self) -> Json {
+        object! {
+            "api" => self.api,
+            "profile" => self.profile,
+            "comment" => self.comment,
+            "enums" => self.enums,
+            "commands" => self.commands
+        }
+    }
+}
+
+impl ToJson for Extension {
+    fn to_json(&self) -> Json {
+        object! {
+            "name" => self.name,
+            "supported" => self.supported,
+            "requires" => self.requires
+        }
+    }
+}
+
+/// A JSON value, along with its path in the document (like `cmds[12].params[0]`) for error
+///  reporting.
+struct Value<'a> {
+    json: &'a Json,
+    path: String,
+}
+
+impl<'a> Value<'a> {
+    fn error<T>(&self, msg: &str) -> Result<T, Error> {
+        Err(Error::Json { path: self.path.clone(), msg: msg.to_string() })
+    }
+
+    /// Returns the member `key` of an object, or `None` if it is missing or `null`.
+    fn opt_field(&self, key: &str) -> Result<Option<Value<'a>>, Error> {
+        let obj = match *self.json {
+            Json::Object(ref obj) => obj,
+            _ => return self.error("expected an object"),
+        };
+        let path = if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        };
+        match obj.get(key) {
+            None | Some(&Json::Null) => Ok(None),
+            Some(json) => Ok(Some(Value { json: json, path: path })),
+        }
+    }
+
+    fn field(&self, key: &str) -> Result<Value<'a>, Error> {
+        match try!(self.opt_field(key)) {
+            Some(value) => Ok(value),
+            None => self.error(&format!("missing member `{}`", key)),
+        }
+    }
+
+    fn string(&self) -> Result<String, Error> {
+        match *self.json {
+            Json::String(ref s) => Ok(s.clone()),
+            _ => self.error("expected a string"),
+        }
+    }
+
+    fn string_field(&self, key: &str) -> Result<String, Error> {
+        try!(self.field(key)).string()
+    }
+
+    fn opt_string_field(&self, key: &str) -> Result<Option<String>, Error> {
+        match try!(self.opt_field(key)) {
+            Some(value) => value.string().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn strings_field(&self, key: &str) -> Result<Vec<String>, Error> {
+        try!(self.field(key)).array(|value| value.string())
+    }
+
+    fn uint(&self) -> Result<u64, Error> {
+        match *self.json {
+            Json::U64(n) => Ok(n),
+            Json::I64(n) if n >= 0 => Ok(n as u64),
+            _ => self.error("expected an unsigned integer"),
+        }
+    }
+
+    fn boolean(&self) -> Result<bool, Error> {
+        match *self.json {
+            Json::Boolean(b) => Ok(b),
+            _ => self.error("expected a boolean"),
+        }
+    }
+
+    fn array<T, F>(&self, f: F) -> Result<Vec<T>, Error> where F: Fn(&Value<'a>) -> Result<T, Error> {
+        match *self.json {
+            Json::Array(ref items) => items.iter().enumerate().map(|(i, json)| {
+                f(&Value { json: json, path: format!("{}[{}]", self.path, i) })
+            }).collect(),
+            _ => self.error("expected an array"),
+        }
+    }
+
+    fn map<T, F>(&self, f: F) -> Result<HashMap<String, T>, Error>
+        where F: Fn(&Value<'a>) -> Result<T, Error>
+    {
+        match *self.json {
+            Json::Object(ref obj) => obj.iter().map(|(key, json)| {
+                let value = Value { json: json, path: format!("{}.{}", self.path, key) };
+                f(&value).map(|v| (key.clone(), v))
+            }).collect(),
+            _ => self.error("expected an object"),
+        }
+    }
+}
+
+fn read_registry(v: &Value) -> Result<Registry, Error> {
+    let schema = try!(v.field("schema"));
+    if try!(schema.uint()) != JSON_SCHEMA_VERSION {
+        return schema.error(&format!("unsupported schema version, expected {}", JSON_SCHEMA_VERSION));
+    }
+
+    let enum_nss = try!(try!(v.field("enum_nss")).array(read_enum_ns));
+    let read_enum_in_block = |v: &Value| {
+        let enm = try!(read_enum(v));
+        if enm.block >= enum_nss.len() {
+            return try!(v.field("block")).error("not the index of an `<enums>` block");
+        }
+        Ok(enm)
+    };
+    let enums = try!(try!(v.field("enums")).array(&read_enum_in_block));
+    let equivalent_enums = try!(try!(v.field("equivalent_enums")).array(|v| {
+        Ok(EquivalentEnum {
+            enm: try!(read_enum_in_block(v)),
+            equivalent_to: try!(v.string_field("equivalent_to")),
+        })
+    }));
+
+    Ok(Registry {
+        groups: try!(try!(v.field("groups")).array(read_group)),
+        types: try!(try!(v.field("types")).array(read_type)),
+        enum_nss: enum_nss,
+        enums: enums,
+        equivalent_enums: equivalent_enums,
+        cmds: try!(try!(v.field("cmds")).array(read_cmd)),
+        features: try!(try!(v.field("features")).array(read_feature)),
+        extensions: try!(try!(v.field("extensions")).array(read_extension)),
+        aliases: try!(try!(v.field("aliases")).map(|v| v.array(|v| v.string()))),
+        provenance: try!(try!(v.field("provenance")).map(read_provenance)),
+    })
+}
+
+fn read_version(v: &Value) -> Result<Version, Error> {
+    match try!(v.string()).parse() {
+        Ok(version) => Ok(version),
+        Err(_) => v.error("expected a version of the form `major.minor`"),
+    }
+}
+
+fn read_provenance(v: &Value) -> Result<Provenance, Error> {
+    Ok(Provenance {
+        introduced_in: try!(v.strings_field("introduced_in")),
+        removed_in: try!(v.strings_field("removed_in")),
+        removed_from_core: match try!(v.opt_field("removed_from_core")) {
+            Some(version) => Some(try!(read_version(&version))),
+            None => None,
+        },
+    })
+}
+
+fn read_group(v: &Value) -> Result<Group, Error> {
+    Ok(Group {
+        name: try!(v.string_field("name")),
+        enums: try!(v.strings_field("enums")),
+    })
+}
+
+fn read_type(v: &Value) -> Result<Type, Error> {
+    Ok(Type {
+        name: try!(v.string_field("name")),
+        api: try!(v.opt_string_field("api")),
+        requires: try!(v.opt_string_field("requires")),
+        comment: try!(v.opt_string_field("comment")),
+        def: try!(read_type_def(&try!(v.field("def")))),
+    })
+}
+
+fn read_type_def(v: &Value) -> Result<TypeDef, Error> {
+    let kind = try!(v.field("kind"));
+    Ok(match &try!(kind.string())[..] {
+        "external" => TypeDef::External,
+        "alias" => TypeDef::Alias(try!(v.string_field("ty"))),
+        "fn_ptr" => TypeDef::FnPtr {
+            ret: try!(v.string_field("ret")),
+            params: try!(try!(v.field("params")).array(read_binding)),
+        },
+        "struct" => TypeDef::Struct(try!(try!(v.field("fields")).array(read_binding))),
+        "opaque" => TypeDef::Opaque,
+        "handle" => TypeDef::Handle,
+        "unsupported" => TypeDef::Unsupported(try!(v.string_field("code"))),
+        _ => return kind.error("unknown kind of type definition"),
+    })
+}
+
+fn read_enum_ns(v: &Value) -> Result<EnumNs, Error> {
+    Ok(EnumNs {
+        namespace: try!(v.string_field("namespace")),
+        group: try!(v.opt_string_field("group")),
+        ty: try!(v.opt_string_field("ty")),
+        start: try!(v.opt_string_field("start")),
+        end: try!(v.opt_string_field("end")),
+        vendor: try!(v.opt_string_field("vendor")),
+        comment: try!(v.opt_string_field("comment")),
+    })
+}
+
+fn read_enum(v: &Value) -> Result<Enum, Error> {
+    Ok(Enum {
+        ident: try!(v.string_field("ident")),
+        value: try!(v.string_field("value")),
+        alias: try!(v.opt_string_field("alias")),
+        ty: try!(v.opt_string_field("ty")),
+        api: try!(v.opt_string_field("api")),
+        block: try!(try!(v.field("block")).uint()) as usize,
+    })
+}
+
+fn read_binding(v: &Value) -> Result<Binding, Error> {
+    Ok(Binding {
+        ident: try!(v.string_field("ident")),
+        ty: try!(v.string_field("ty")),
+        group: try!(v.opt_string_field("group")),
+        len: try!(v.opt_string_field("len")).map(|len| Len::parse(&len)),
+        class: try!(v.opt_string_field("class")),
+        kind: try!(v.opt_string_field("kind")),
+    })
+}
+
+fn read_cmd(v: &Value) -> Result<Cmd, Error> {
+    Ok(Cmd {
+        proto: try!(read_binding(&try!(v.field("proto")))),
+        params: try!(try!(v.field("params")).array(read_binding)),
+        is_safe: try!(try!(v.field("is_safe")).boolean()),
+        alias: try!(v.opt_string_field("alias")),
+        vecequiv: try!(v.opt_string_field("vecequiv")),
+        glx: match try!(v.opt_field("glx")) {
+            Some(glx) => Some(try!(read_glx_opcode(&glx))),
+            None => None,
+        },
+    })
+}
+
+fn read_glx_opcode(v: &Value) -> Result<GlxOpcode, Error> {
+    Ok(GlxOpcode {
+        ty: try!(v.string_field("ty")),
+        opcode: try!(v.string_field("opcode")),
+        name: try!(v.opt_string_field("name")),
+        comment: try!(v.opt_string_field("comment")),
+    })
+}
+
+fn read_feature(v: &Value) -> Result<Feature, Error> {
+    Ok(Feature {
+        api: try!(v.string_field("api")),
+        name: try!(v.string_field("name")),
+        number: try!(read_version(&try!(v.field("number")))),
+        requires: try!(try!(v.field("requires")).array(read_require)),
+        removes: try!(try!(v.field("removes")).array(read_remove)),
+    })
+}
+
+fn read_require(v: &Value) -> Result<Require, Error> {
+    Ok(Require {
+        api: try!(v.opt_string_field("api")),
+        profile: try!(v.opt_string_field("profile")),
+        comment: try!(v.opt_string_field("comment")),
+        enums: try!(v.strings_field("enums")),
+        commands: try!(v.strings_field("commands")),
+    })
+}
+
+fn read_remove(v: &Value) -> Result<Remove, Error> {
+    Ok(Remove {
+        api: try!(v.opt_string_field("api")),
+        profile: try!(v.opt_string_field("profile")),
+        comment: try!(v.opt_string_field("comment")),
+        enums: try!(v.strings_field("enums")),
+        commands: try!(v.strings_field("commands")),
+    })
+}
+
+fn read_extension(v: &Value) -> Result<Extension, Error> {
+    Ok(Extension {
+        name: try!(v.string_field("name")),
+        supported: try!(v.strings_field("supported")),
+        requires: try!(try!(v.field("requires")).array(read_require)),
+    })
+}
@@ -39,7 +39,8 @@
 //!     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
 //!                                     gl_generator::registry::Ns::Gl,
 //!                                     gl_generator::Fallbacks::All,
-//!                                     khronos_api::GL_XML, vec![], "4.5", "core",
+//!                                     khronos_api::GL_XML, vec![],
+//!                                     Some(gl_generator::Version::new(4, 5)), "core",
 //!                                     &mut file).unwrap();
 //! }
 //! ~~~
@@ -50,32 +51,108 @@
 //! include!(concat!(env!("OUT_DIR"), "/gl_bindings.rs"));
 //! ~~~
 //!
+//! ## Configuring the bindings
+//!
+//! `Bindings` configures the same bindings one setting at a time, and is where
+//!  new settings are added. Only the API, the version and the profile are
+//!  required:
+//!
+//! ~~~ignore
+//! use gl_generator::{Bindings, Fallbacks, Profile, Version};
+//! use gl_generator::registry::Ns;
+//!
+//! Bindings::new(Ns::Gl, Version::new(4, 5), Profile::Core)
+//!     .extensions(vec!["GL_ARB_debug_output".to_string()])
+//!     .fallbacks(Fallbacks::None)
+//!     .generator(gl_generator::StructGenerator)
+//!     .write_to(&mut file)
+//!     .unwrap();
+//! ~~~
+//!
+//! `Bindings::registry` reads and filters the registry without writing
+//!  anything, to check the configuration up front.
+//!
+//! ## Using another registry file
+//!
+//! The `source` argument can be a path instead of one of the files bundled with
+//!  `khronos_api`, for example to use a newer `gl.xml`. With
+//!  `gl_generator::Source::bundled("gl.xml", khronos_api::GL_XML)`, the bundled
+//!  file is used unless the `GL_GENERATOR_XML_DIR` environment variable names a
+//!  directory to read `gl.xml` from. `Bindings::rerun_if_changed` tells Cargo
+//!  to run the build script again when the variable or the file that is read
+//!  changes.
+//!
+//! ## Dumping the registry
+//!
+//! Passing `JsonGenerator` with the same arguments writes the registry the
+//!  bindings were generated from as JSON, for example to `gl_bindings.json`
+//!  next to `gl_bindings.rs`. Tools can read it back with
+//!  `gl_generator::registry::Registry::from_json` instead of parsing the XML
+//!  again.
+//!
+//! ## Deprecations
+//!
+//! Wrapping the generator in `generators::WithOptions` with `deprecations` enabled
+//!  marks the commands and enums that a later version removes from the core
+//!  profile with `#[deprecated]`. For example, `glBegin` in GL 3.0 or in
+//!  compatibility bindings gets
+//!  `#[deprecated(note = "removed from core profile in GL 3.2")]`, so that the
+//!  compiler warns about each use:
+//!
+//! ~~~ignore
+//! let options = gl_generator::generators::Options { deprecations: true, .. Default::default() };
+//! gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, options), ...)
+//! ~~~
+//!
+//! With `enum_aliases`, the enums that the registry declares as aliases of the
+//!  generated ones, or the other way around, are generated too, documented with
+//!  the enum they are equivalent to.
+//!
+//! With `vec_helpers`, an `arrays` module has a helper for each vector command
+//!  whose array has a fixed length, like `arrays::Color3fv(&[GLfloat; 3])`,
+//!  which calls `Color3fv` with a pointer to the array.
+//!
+//! With `enum_groups`, the parameters that name a `<group>` of enums take a
+//!  newtype of the group, like `types::PrimitiveType` for the `mode` of
+//!  `DrawArrays`, whose constants are the enums of the group. The groups of bit
+//!  flags, like `types::ClearBufferMask`, can be combined with `|`.
+//!
 //! ## Arguments
 //!
 //! Each field can be specified at most once, or not at all. If the field is not
 //! specified, then a default value will be used.
 //!
 //! - `api`: The API to generate. Can be either `"gl"`, `"gles1"`, `"gles2"`,
-//!   `"wgl"`, `"glx"`, `"egl"`. Defaults to `"gl"`.
+//!   `"glsc2"`, `"glcore"`, `"wgl"`, `"glx"`, `"egl"`. Defaults to `"gl"`. Other
+//!   APIs can be described with a `registry::ApiInfo` and passed as
+//!   `Ns::Custom`.
 //! - `profile`: Can be either `"core"` or `"compatibility"`. Defaults to
 //!   `"core"`. `"core"` will only include all functions supported by the
 //!   requested version it self, while `"compatibility"` will include all the
-//!   functions from previous versions as well.
-//! - `version`: The requested API version. This is usually in the form
-//!   `"major.minor"`. Defaults to `"1.0"`
+//!   functions from previous versions as well. Any other value is an error.
+//!   Only OpenGL has profiles: the other APIs ignore this argument.
+//! - `version`: The requested API version, for example `Some(Version::new(4, 5))`.
+//!   `None` selects the latest version of the API that the registry defines.
 //! - `generator`: The type of loader to generate. Can be either `"static"`,
-//!   `"global"`, or `"struct"`. Defaults to `"static"`.
+//!   `"global"`, or `"struct"`. Defaults to `"static"`. `SafeWrapperGenerator`
+//!   adds safe methods taking slices, like `gen_buffers(&mut [GLuint])`, to the
+//!   struct bindings. `MockGenerator` writes the same API as `"struct"`, but
+//!   records the calls instead of making them, for tests without a GPU.
+//!   `JsonGenerator` writes the filtered registry as JSON instead of Rust code.
 //! - `extensions`: Extra extensions to include in the bindings. These are
-//!   specified as a list of strings. Defaults to `[]`.
+//!   specified as a list of strings, or as an `ExtensionSelector` to select them
+//!   by pattern or by vendor. Defaults to `[]`.
+//! - `fallbacks`: The commands the loaders try when a command cannot be loaded.
+//!   `Fallbacks::All` uses all the aliases of the registry, and
+//!   `Fallbacks::None` none of them. A `FallbackPolicy` orders them by vendor,
+//!   leaves out some commands or vendors, and adds fallbacks that the registry
+//!   doesn't know about.
 //!
 //! ## About EGL
 //!
 //! When you generate bindings for EGL, the following platform-specific types must be declared
 //!  *at the same level where you include the bindings*:
 //!
-//! - `khronos_utime_nanoseconds_t`
-//! - `khronos_uint64_t`
-//! - `khronos_ssize_t`
 //! - `EGLNativeDisplayType`
 //! - `EGLNativePixmapType`
 //! - `EGLNativeWindowType`
@@ -90,43 +167,62 @@ extern crate log;
 extern crate khronos_api;
 
 use generators::Generator;
-use registry::{Registry, Filter, Ns};
 
 use std::io;
 
-pub use registry::Fallbacks;
+pub use bindings::Bindings;
+pub use error::Error;
+pub use json::JSON_SCHEMA_VERSION;
+pub use registry::{ExtensionSelector, FallbackPolicy, Fallbacks, Profile, Version};
+pub use source::{Source, XML_DIR_VAR};
 pub use generators::debug_struct_gen::DebugStructGenerator;
 pub use generators::global_gen::GlobalGenerator;
+pub use generators::json_gen::JsonGenerator;
+pub use generators::mock_gen::MockGenerator;
+pub use generators::safe_wrapper_gen::SafeWrapperGenerator;
 pub use generators::static_gen::StaticGenerator;
 pub use generators::static_struct_gen::StaticStructGenerator;
 pub use generators::struct_gen::StructGenerator;
 
+mod bindings;
+mod diff;
+mod error;
 pub mod generators;
+mod json;
 
 #[allow(dead_code)]
 pub mod registry;
+mod source;
 
 /// Public function that generates Rust source code.
-pub fn generate_bindings<G, W>(generator: G, ns: registry::Ns, fallbacks: Fallbacks, source: &[u8],
-                               extensions: Vec<String>, version: &str, profile: &str,
-                               dest: &mut W) -> io::Result<()> where G: Generator, W: io::Write
+///
+/// `source` is either the content of a registry file, like `khronos_api::GL_XML`, or a `Path`.
+///  `extensions` is either a list of extension names or an `ExtensionSelector`. On success, the
+///  names of the extensions that have been included in the bindings are returned.
+///
+/// This is a shorthand for configuring `Bindings`, which can also set the options of the
+///  generator. New options are only added to `Bindings`.
+///
+/// Returns an error if the registry could not be read or parsed, if the requested version or
+///  extensions are not available for the API, or if writing to `dest` failed.
+pub fn generate_bindings<'a, G, S, E, W>(generator: G, ns: registry::Ns, fallbacks: Fallbacks,
+                                         source: S, extensions: E, version: Option<Version>,
+                                         profile: &str, dest: &mut W) -> Result<Vec<String>, Error>
+                                         where G: Generator, S: Into<Source<'a>>,
+                                               E: Into<ExtensionSelector>, W: io::Write
 {
-    // Get generator field values, using default values if they have not been
-    // specified
-    let filter = Some(Filter {
-        api: ns.to_string(),
-        fallbacks: fallbacks,
-        extensions: extensions,
-        version: version.to_string(),
-        profile: profile.to_string(),
-    });
-
-    // Generate the registry of all bindings
-    let registry = {
-        use std::io::BufReader;
-        let reader = BufReader::new(source);
-        Registry::from_xml(reader, ns, filter)
+    // the profile implied by the API replaces the requested one, which isn't parsed, and the
+    // APIs other than OpenGL ignore it
+    let profile = match ns.info().profile {
+        Some(profile) => profile,
+        None if ns.info().api == "gl" => try!(profile.parse()),
+        None => profile.parse().unwrap_or(Profile::Core),
     };
 
-    generator.write(&registry, ns, dest)
+    Bindings::new(ns, version, profile)
+        .source(source)
+        .extensions(extensions)
+        .fallbacks(fallbacks)
+        .generator(generator)
+        .write_to(dest)
 }
@@ -27,38 +27,273 @@ use std::slice::Iter;
 use std::io;
 
 use self::xml::attribute::OwnedAttribute;
+use self::xml::common::HasPosition;
 use self::xml::reader::events::XmlEvent;
 
-use self::Ns::{Gl, Glx, Wgl, Egl, Gles1, Gles2};
+use Error;
 
+pub use diff::{diff, Change, ChangeKind, Diff, Reason};
+
+/// The API to generate bindings for.
+///
+/// APIs that are not listed here can be described with an `ApiInfo` and used through `Custom`.
 #[derive(Copy, Clone)]
-pub enum Ns { Gl, Glx, Wgl, Egl, Gles1, Gles2 }
+pub enum Ns {
+    Gl,
+    Glx,
+    Wgl,
+    Egl,
+    Gles1,
+    Gles2,
+    /// OpenGL SC 2.0.
+    Glsc2,
+    /// The core profile of OpenGL, with the extensions that the registry marks as supported by
+    ///  `glcore`.
+    Glcore,
+    Custom(&'static ApiInfo),
+}
+
+/// How an API is named in the registry and in the generated bindings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ApiInfo {
+    /// The name of the API, like `gles2`. This is the name extensions use in their `supported`
+    ///  attribute.
+    pub name: &'static str,
+    /// The API of the `<feature>` elements that define the versions, and the value of the `api`
+    ///  attributes that apply. Only differs from `name` for pseudo-APIs like `glcore`.
+    pub api: &'static str,
+    /// The profile implied by the API, if any. It replaces the profile passed to
+    ///  `generate_bindings`.
+    pub profile: Option<Profile>,
+    /// The prefix of the commands, like `gl` or `glX`.
+    pub cmd_prefix: &'static str,
+    /// The prefix of the enums, like `GL_` or `GLX_`.
+    pub enum_prefix: &'static str,
+    /// The name of the struct generated by the struct generators, like `Gl`.
+    pub struct_name: &'static str,
+}
+
+static GL: ApiInfo = ApiInfo {
+    name: "gl", api: "gl", profile: None, cmd_prefix: "gl", enum_prefix: "GL_", struct_name: "Gl",
+};
+static GLX: ApiInfo = ApiInfo {
+    name: "glx", api: "glx", profile: None, cmd_prefix: "glX", enum_prefix: "GLX_", struct_name: "Glx",
+};
+static WGL: ApiInfo = ApiInfo {
+    name: "wgl", api: "wgl", profile: None, cmd_prefix: "wgl", enum_prefix: "WGL_", struct_name: "Wgl",
+};
+static EGL: ApiInfo = ApiInfo {
+    name: "egl", api: "egl", profile: None, cmd_prefix: "egl", enum_prefix: "EGL_", struct_name: "Egl",
+};
+static GLES1: ApiInfo = ApiInfo {
+    name: "gles1", api: "gles1", profile: None, cmd_prefix: "gl", enum_prefix: "GL_", struct_name: "Gles1",
+};
+static GLES2: ApiInfo = ApiInfo {
+    name: "gles2", api: "gles2", profile: None, cmd_prefix: "gl", enum_prefix: "GL_", struct_name: "Gles2",
+};
+static GLSC2: ApiInfo = ApiInfo {
+    name: "glsc2", api: "glsc2", profile: None, cmd_prefix: "gl", enum_prefix: "GL_", struct_name: "Glsc2",
+};
+static GLCORE: ApiInfo = ApiInfo {
+    name: "glcore", api: "gl", profile: Some(Profile::Core), cmd_prefix: "gl", enum_prefix: "GL_",
+    struct_name: "Gl",
+};
+
+/// Which fallbacks the loaders try when a command cannot be loaded.
+#[derive(Clone, Debug)]
+pub enum Fallbacks {
+    /// Every command the registry aliases with the command.
+    All,
+    /// No fallbacks.
+    None,
+    /// The fallbacks of the registry, filtered, reordered and extended by a policy.
+    Policy(FallbackPolicy),
+}
+
+impl From<FallbackPolicy> for Fallbacks {
+    fn from(policy: FallbackPolicy) -> Fallbacks {
+        Fallbacks::Policy(policy)
+    }
+}
+
+/// Chooses the fallbacks of the commands, and the order they are tried in.
+///
+/// Commands are identified without prefix, like `GenFramebuffersEXT`, and their vendor is the
+///  suffix of their name, like `EXT`. The deny lists only apply to the fallbacks of the registry,
+///  not to the extra ones.
+#[derive(Clone, Debug, Default)]
+pub struct FallbackPolicy {
+    /// Vendors in order of preference, like `["ARB", "EXT"]`. The fallbacks of these vendors are
+    ///  tried first, in this order, followed by the others in the order of the registry.
+    pub prefer: Vec<String>,
+    /// Names or patterns of the commands never to use as fallbacks, like `"*APPLE"`.
+    pub deny: Vec<String>,
+    /// Vendors whose commands are never used as fallbacks, like `"NV"`.
+    pub deny_vendors: Vec<String>,
+    /// Extra fallbacks of commands, which the registry doesn't declare as aliases, like
+    ///  `DrawArraysInstancedANGLE` for `DrawArraysInstanced`. They are tried after the fallbacks
+    ///  of the registry, unless their vendor is preferred.
+    pub extra: HashMap<String, Vec<String>>,
+}
+
+impl FallbackPolicy {
+    /// Applies the policy to the complete fallbacks of a registry.
+    fn apply(&self, aliases: HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+        // the commands that have aliases, whose names the aliases can start with
+        let cmds: HashSet<String> = aliases.keys().cloned().collect();
+        let mut aliases: HashMap<String, Vec<String>> = aliases.into_iter().map(|(cmd, fallbacks)| {
+            let fallbacks = fallbacks.into_iter().filter(|name| !self.is_denied(name, &cmds)).collect();
+            (cmd, fallbacks)
+        }).collect();
+        for (cmd, extra) in self.extra.iter() {
+            merge_names(aliases.entry(cmd.clone()).or_insert_with(Vec::new), extra.clone());
+        }
+
+        for fallbacks in aliases.values_mut() {
+            // stable, so the fallbacks of the same rank keep the order of the registry
+            fallbacks.sort_by_key(|name| self.rank(name, &cmds));
+        }
+        aliases.into_iter().filter(|&(_, ref fallbacks)| !fallbacks.is_empty()).collect()
+    }
+
+    fn is_denied(&self, name: &str, cmds: &HashSet<String>) -> bool {
+        self.deny.iter().any(|pattern| glob_match(pattern, name)) ||
+            self.deny_vendors.iter().any(|vendor| vendor == vendor_suffix(name, cmds))
+    }
+
+    /// Returns the position of the vendor of a command in the preferences, or the number of
+    ///  preferences if it has none.
+    fn rank(&self, name: &str, cmds: &HashSet<String>) -> usize {
+        self.prefer.iter().position(|vendor| vendor == vendor_suffix(name, cmds)).unwrap_or(self.prefer.len())
+    }
+}
 
-pub enum Fallbacks { All, None }
+/// Returns the vendor suffix of the command `name`, like `EXT` for `FooEXT`, or an empty string.
+///
+/// The suffix follows the name of the command without vendor. That is the longest of `cmds` that
+///  `name` starts with, like `TexImage3D` for `TexImage3DOES`, or else `name` without its
+///  trailing uppercase letters, where a digit and the `D` after it are part of the name.
+fn vendor_suffix<'a>(name: &'a str, cmds: &HashSet<String>) -> &'a str {
+    let is_suffix = |c: char| c.is_uppercase() || c.is_digit(10);
+    let start = name.rfind(|c: char| !is_suffix(c)).map_or(0, |i| i + 1);
+    for i in (start .. name.len()).rev() {
+        if cmds.contains(&name[.. i]) {
+            return &name[i ..];
+        }
+    }
+    match name[start ..].rfind(|c: char| c.is_digit(10)) {
+        // a dimension, like the `3D` of `TexImage3D`
+        Some(i) if name[start + i + 1 ..].starts_with('D') => &name[start + i + 2 ..],
+        Some(i) => &name[start + i + 1 ..],
+        None => &name[start ..],
+    }
+}
+
+/// The version number of an API, such as `4.5` for OpenGL 4.5.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Version {
+    pub fn new(major: u8, minor: u8) -> Version {
+        Version { major: major, minor: minor }
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    /// Parses a version of the form `"major.minor"`.
+    fn from_str(s: &str) -> Result<Version, Error> {
+        let mut parts = s.split('.');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(major), Some(minor), None) => {
+                match (major.parse(), minor.parse()) {
+                    (Ok(major), Ok(minor)) => Ok(Version::new(major, minor)),
+                    _ => Err(Error::InvalidVersion(s.to_string())),
+                }
+            }
+            _ => Err(Error::InvalidVersion(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// A profile of OpenGL.
+///
+/// Only OpenGL 3.2 and later have profiles. The other APIs (OpenGL ES, EGL, GLX and WGL) don't
+///  have any, and ignore the requested profile. Their `profile` attributes, like the `common`
+///  profile of OpenGL ES 1, don't select anything.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Profile {
+    /// Leaves out everything that the registry removes from the core profile, like `glBegin`.
+    Core,
+    /// Keeps everything that earlier versions defined.
+    Compatibility,
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    /// Parses `"core"` or `"compatibility"`.
+    fn from_str(s: &str) -> Result<Profile, Error> {
+        match s {
+            "core" => Ok(Profile::Core),
+            "compatibility" => Ok(Profile::Compatibility),
+            _ => Err(Error::InvalidProfile(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Profile::Core => write!(fmt, "core"),
+            Profile::Compatibility => write!(fmt, "compatibility"),
+        }
+    }
+}
 
 impl Ns {
-    pub fn fmt_struct_name(&self) -> &str {
+    /// Returns the description of the API.
+    pub fn info(&self) -> &'static ApiInfo {
         match *self {
-            Gl  => "Gl",
-            Glx => "Glx",
-            Wgl => "Wgl",
-            Egl => "Egl",
-            Gles1 => "Gles1",
-            Gles2 => "Gles2",
+            Ns::Gl => &GL,
+            Ns::Glx => &GLX,
+            Ns::Wgl => &WGL,
+            Ns::Egl => &EGL,
+            Ns::Gles1 => &GLES1,
+            Ns::Gles2 => &GLES2,
+            Ns::Glsc2 => &GLSC2,
+            Ns::Glcore => &GLCORE,
+            Ns::Custom(info) => info,
         }
     }
+
+    pub fn fmt_struct_name(&self) -> &str {
+        self.info().struct_name
+    }
 }
 
 impl FromStr for Ns {
     type Err = ();
     fn from_str(s: &str) -> Result<Ns, ()> {
         match s {
-            "gl"  => Ok(Gl),
-            "glx" => Ok(Glx),
-            "wgl" => Ok(Wgl),
-            "egl" => Ok(Egl),
-            "gles1" => Ok(Gles1),
-            "gles2" => Ok(Gles2),
+            "gl"  => Ok(Ns::Gl),
+            "glx" => Ok(Ns::Glx),
+            "wgl" => Ok(Ns::Wgl),
+            "egl" => Ok(Ns::Egl),
+            "gles1" => Ok(Ns::Gles1),
+            "gles2" => Ok(Ns::Gles2),
+            "glsc2" => Ok(Ns::Glsc2),
+            "glcore" => Ok(Ns::Glcore),
             _     => Err(()),
         }
     }
@@ -66,14 +301,7 @@ impl FromStr for Ns {
 
 impl fmt::Display for Ns {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Gl  => write!(fmt, "gl"),
-            Glx => write!(fmt, "glx"),
-            Wgl => write!(fmt, "wgl"),
-            Egl => write!(fmt, "egl"),
-            Gles1 => write!(fmt, "gles1"),
-            Gles2 => write!(fmt, "gles2"),
-        }
+        write!(fmt, "{}", self.info().name)
     }
 }
 
@@ -82,21 +310,11 @@ fn trim_str<'a>(s: &'a str, trim: &str) -> &'a str {
 }
 
 fn trim_enum_prefix<'a>(ident: &'a str, ns: Ns) -> &'a str {
-    match ns {
-        Gl | Gles1 | Gles2 => trim_str(ident, "GL_"),
-        Glx => trim_str(ident, "GLX_"),
-        Wgl =>  trim_str(ident, "WGL_"),
-        Egl =>  trim_str(ident, "EGL_"),
-    }
+    trim_str(ident, ns.info().enum_prefix)
 }
 
 fn trim_cmd_prefix<'a>(ident: &'a str, ns: Ns) -> &'a str {
-    match ns {
-        Gl | Gles1 | Gles2 => trim_str(ident, "gl"),
-        Glx => trim_str(ident, "glX"),
-        Wgl =>  trim_str(ident, "wgl"),
-        Egl =>  trim_str(ident, "egl"),
-    }
+    trim_str(ident, ns.info().cmd_prefix)
 }
 
 fn merge_map(a: &mut HashMap<String, Vec<String>>, b: HashMap<String, Vec<String>>) {
@@ -110,16 +328,41 @@ fn merge_map(a: &mut HashMap<String, Vec<String>>, b: HashMap<String, Vec<String
 
 pub struct Registry {
     pub groups: Vec<Group>,
+    pub types: Vec<Type>,
+    /// The `<enums>` blocks of the registry, in order. Filtering keeps all of them.
+    pub enum_nss: Vec<EnumNs>,
     pub enums: Vec<Enum>,
+    /// The enums that the selected features and extensions don't require, but that are aliases
+    ///  of required enums, or the other way around. Only filled in when the registry is filtered.
+    pub equivalent_enums: Vec<EquivalentEnum>,
     pub cmds: Vec<Cmd>,
     pub features: Vec<Feature>,
     pub extensions: Vec<Extension>,
+    /// The fallbacks of each command: the commands that are connected to it by `<alias>`
+    ///  elements, directly or through other commands, in both directions. For example,
+    ///  `GenFramebuffers` and `GenFramebuffersEXT` are fallbacks of each other.
     pub aliases: HashMap<String, Vec<String>>,
+    /// The features and extensions that added or removed each command and enum, by identifier.
+    ///  Only filled in when the registry is filtered.
+    pub provenance: HashMap<String, Provenance>,
+}
+
+/// Where a command or an enum comes from.
+#[derive(Clone, Debug, Default)]
+pub struct Provenance {
+    /// The names of the features (like `GL_VERSION_4_3`) and extensions (like `GL_KHR_debug`)
+    ///  that require the command or enum.
+    pub introduced_in: Vec<String>,
+    /// The names of the features that remove the command or enum.
+    pub removed_in: Vec<String>,
+    /// The first version whose core profile removes the command or enum, if the registry still
+    ///  has it. This is the case for compatibility profiles, and for versions before the removal.
+    pub removed_from_core: Option<Version>,
 }
 
 impl Registry {
     /// Generate a registry from the supplied XML string
-    pub fn from_xml<R: io::Read>(data: R, ns: Ns, filter: Option<Filter>) -> Registry {
+    pub fn from_xml<R: io::Read>(data: R, ns: Ns, filter: Option<Filter>) -> Result<Registry, Error> {
         use std::io::BufReader;
         let data = BufReader::new(data);
 
@@ -127,6 +370,7 @@ impl Registry {
             ns: ns,
             filter: filter,
             port: RefCell::new(xml::reader::EventReader::new(data)),
+            path: RefCell::new(Vec::new()),
         }.consume_registry()
     }
 
@@ -143,6 +387,100 @@ impl Registry {
         tys
     }
 
+    /// Returns the definition of the type with the given name.
+    ///
+    /// If the registry contains several API-specific definitions of the type, the first one is
+    ///  returned. Filtering a registry only keeps the definition for the filtered API.
+    pub fn get_type(&self, name: &str) -> Option<&Type> {
+        self.types.iter().find(|ty| ty.name == name)
+    }
+
+    /// Returns the names of the features and extensions that introduced a command or an enum,
+    ///  given its identifier without prefix (like `DebugMessageCallback` or `STACK_OVERFLOW`).
+    pub fn introduced_in(&self, ident: &str) -> &[String] {
+        self.provenance.get(ident).map_or(&[], |p| &p.introduced_in[..])
+    }
+
+    /// Returns the names of the features that removed a command or an enum, given its identifier
+    ///  without prefix.
+    pub fn removed_in(&self, ident: &str) -> &[String] {
+        self.provenance.get(ident).map_or(&[], |p| &p.removed_in[..])
+    }
+
+    /// Returns the first version of the core profile that removes a command or an enum of the
+    ///  registry, given its identifier without prefix.
+    pub fn removed_from_core(&self, ident: &str) -> Option<Version> {
+        self.provenance.get(ident).and_then(|p| p.removed_from_core)
+    }
+
+    /// Follows the `alias` attributes from the enum `ident` and returns the enum at the end of the
+    ///  chain, or `None` if the registry doesn't define `ident`.
+    ///
+    /// Returns an error if two enums of the chain have different values, or if the chain loops.
+    pub fn resolve_enum(&self, ident: &str) -> Result<Option<&Enum>, Error> {
+        let by_ident = enums_by_ident(self.all_enums());
+        match by_ident.get(ident) {
+            Some(enm) => alias_chain(&by_ident, enm).map(|chain| chain.last().cloned()),
+            None => Ok(None),
+        }
+    }
+
+    /// Checks the `alias` attributes of all the enums, like `resolve_enum` does for one of them.
+    ///
+    /// Filtering a registry doesn't fail on bad aliases: it logs them, and doesn't treat the
+    ///  enums involved as equivalent.
+    pub fn check_enum_aliases(&self) -> Result<(), Error> {
+        let by_ident = enums_by_ident(self.all_enums());
+        for enm in self.all_enums() {
+            try!(alias_chain(&by_ident, enm));
+        }
+        Ok(())
+    }
+
+    fn all_enums<'a>(&'a self) -> Box<Iterator<Item = &'a Enum> + 'a> {
+        Box::new(self.enums.iter().chain(self.equivalent_enums.iter().map(|e| &e.enm)))
+    }
+
+    /// Returns the fallbacks of a command, given its identifier without prefix. The direct aliases
+    ///  come first.
+    pub fn fallbacks(&self, ident: &str) -> &[String] {
+        self.aliases.get(ident).map_or(&[], |fallbacks| &fallbacks[..])
+    }
+
+    /// Returns the enums of a group that are in the registry, in the order of the group, given
+    ///  the name of the group, like `PrimitiveType`. Returns `None` if the registry doesn't define
+    ///  the group.
+    pub fn group_enums(&self, group: &str) -> Option<Vec<&Enum>> {
+        self.groups.iter().find(|g| g.name == group).map(|group| {
+            let enums: HashMap<&str, &Enum> = self.enum_iter().map(|e| (&e.ident[..], e)).collect();
+            group.enums.iter().filter_map(|ident| enums.get(&ident[..]).cloned()).collect()
+        })
+    }
+
+    /// Returns true if the enums of a group are bit flags to combine with `|`: if the group is
+    ///  named like `ClearBufferMask`, or if an `<enums type="bitmask">` block holds the group or
+    ///  one of its enums.
+    pub fn is_bitmask_group(&self, group: &str) -> bool {
+        group.ends_with("Mask") ||
+            self.enum_nss.iter().any(|ns| ns.is_bitmask() && ns.group.as_ref().map_or(false, |g| g == group)) ||
+            self.group_enums(group).map_or(false, |enums| enums.iter().any(|e| self.enum_ns(e).is_bitmask()))
+    }
+
+    /// Returns the command that takes the arguments of a command as an array, given the
+    ///  identifier of the command without prefix, like `Color3fv` for `Color3f`. Returns `None`
+    ///  if the registry has no such command.
+    pub fn vector_equivalent(&self, ident: &str) -> Option<&Cmd> {
+        self.cmd_iter()
+            .find(|c| c.proto.ident == ident)
+            .and_then(|c| c.vecequiv.as_ref())
+            .and_then(|vecequiv| self.cmd_iter().find(|c| &c.proto.ident == vecequiv))
+    }
+
+    /// Returns the `<enums>` block that defines an enum of the registry.
+    pub fn enum_ns(&self, enm: &Enum) -> &EnumNs {
+        &self.enum_nss[enm.block]
+    }
+
     pub fn enum_iter<'a>(&'a self) -> EnumIterator<'a> {
         EnumIterator {
             seen: HashSet::new(),
@@ -158,16 +496,193 @@ impl Registry {
     }
 }
 
+impl Registry {
+    /// Merges another registry into this one, for example the XML of a vendor into the Khronos
+    ///  registry.
+    ///
+    /// Commands, enums and types that both registries define identically are kept once. Defining
+    ///  one of them differently in the two registries is an error: a command with another
+    ///  signature, an enum with another value or a type with another definition. Enums and types
+    ///  that are specific to different APIs don't conflict. Groups, alias lists and provenance
+    ///  are merged. Features and extensions are matched by name, and the ones of `self` are kept.
+    pub fn merge(mut self, other: Registry) -> Result<Registry, Error> {
+        let offset = self.enum_nss.len();
+        self.enum_nss.extend(other.enum_nss.into_iter());
+
+        let mut enums: HashMap<(String, Option<String>), usize> = HashMap::new();
+        for (i, enm) in self.enums.iter().enumerate() {
+            enums.entry((enm.ident.clone(), enm.api.clone())).or_insert(i);
+        }
+        for enm in other.enums.into_iter() {
+            if let Some(&i) = enums.get(&(enm.ident.clone(), enm.api.clone())) {
+                let existing = &self.enums[i];
+                if existing.value != enm.value || existing.ty != enm.ty {
+                    return Err(Error::ConflictingEnum {
+                        name: enm.ident,
+                        values: vec![existing.value.clone(), enm.value],
+                    });
+                }
+                continue;
+            }
+            // indexed too, so that the duplicates of `other` are merged the same way
+            enums.insert((enm.ident.clone(), enm.api.clone()), self.enums.len());
+            self.enums.push(Enum { block: enm.block + offset, .. enm });
+        }
+        for mut equivalent in other.equivalent_enums.into_iter() {
+            if !self.all_enums().any(|e| e.ident == equivalent.enm.ident) {
+                equivalent.enm.block += offset;
+                self.equivalent_enums.push(equivalent);
+            }
+        }
+
+        let mut cmds: HashMap<String, usize> = HashMap::new();
+        for (i, cmd) in self.cmds.iter().enumerate() {
+            cmds.entry(cmd.proto.ident.clone()).or_insert(i);
+        }
+        for cmd in other.cmds.into_iter() {
+            if let Some(&i) = cmds.get(&cmd.proto.ident) {
+                let existing = &mut self.cmds[i];
+                if existing.signature() != cmd.signature() {
+                    return Err(Error::ConflictingCommand {
+                        name: cmd.proto.ident.clone(),
+                        signatures: vec![existing.signature(), cmd.signature()],
+                    });
+                }
+                if existing.alias.is_none() { existing.alias = cmd.alias; }
+                if existing.vecequiv.is_none() { existing.vecequiv = cmd.vecequiv; }
+                if existing.glx.is_none() { existing.glx = cmd.glx; }
+                continue;
+            }
+            cmds.insert(cmd.proto.ident.clone(), self.cmds.len());
+            self.cmds.push(cmd);
+        }
+
+        for ty in other.types.into_iter() {
+            match self.types.iter().find(|t| t.name == ty.name && t.api == ty.api) {
+                Some(existing) if existing.def != ty.def => {
+                    return Err(Error::ConflictingType { name: ty.name });
+                }
+                Some(_) => continue,
+                None => (),
+            }
+            self.types.push(ty);
+        }
+
+        for group in other.groups.into_iter() {
+            match self.groups.iter().position(|g| g.name == group.name) {
+                Some(i) => merge_names(&mut self.groups[i].enums, group.enums),
+                None => self.groups.push(group),
+            }
+        }
+
+        for feature in other.features.into_iter() {
+            if !self.features.iter().any(|f| f.name == feature.name) {
+                self.features.push(feature);
+            }
+        }
+        for ext in other.extensions.into_iter() {
+            if !self.extensions.iter().any(|e| e.name == ext.name) {
+                self.extensions.push(ext);
+            }
+        }
+
+        for (ident, aliases) in other.aliases.into_iter() {
+            merge_names(self.aliases.entry(ident).or_insert_with(Vec::new), aliases);
+        }
+        self.aliases = complete_aliases(self.aliases);
+        for (ident, other) in other.provenance.into_iter() {
+            let provenance = self.provenance.entry(ident).or_insert_with(Provenance::default);
+            merge_names(&mut provenance.introduced_in, other.introduced_in);
+            merge_names(&mut provenance.removed_in, other.removed_in);
+            provenance.removed_from_core = earliest(provenance.removed_from_core, other.removed_from_core);
+        }
+
+        Ok(self)
+    }
+}
+
+/// Turns a map from commands to the commands that declare themselves as their aliases into a map
+///  from each of these commands to all the others they are connected to, in both directions and
+///  through any number of aliases. The commands are listed by distance, and the ones of the
+///  original map come first.
+fn complete_aliases(aliases: HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+    let mut keys: Vec<&String> = aliases.keys().collect();
+    keys.sort();
+
+    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+    for &key in keys.iter() {
+        let neighbors = graph.entry(key).or_insert_with(Vec::new);
+        for alias in aliases[key].iter() {
+            if !neighbors.contains(&&alias[..]) {
+                neighbors.push(alias);
+            }
+        }
+    }
+    for &key in keys.iter() {
+        for alias in aliases[key].iter() {
+            let neighbors = graph.entry(alias).or_insert_with(Vec::new);
+            if !neighbors.contains(&&key[..]) {
+                neighbors.push(key);
+            }
+        }
+    }
+
+    graph.keys().map(|&cmd| {
+        let mut found = vec![cmd];
+        let mut next = 0;
+        while next < found.len() {
+            for &neighbor in graph[found[next]].iter() {
+                if !found.contains(&neighbor) {
+                    found.push(neighbor);
+                }
+            }
+            next += 1;
+        }
+        (cmd.to_string(), found[1 ..].iter().map(|name| name.to_string()).collect())
+    }).collect()
+}
+
+/// Appends the names of `b` that are not already in `a`.
+fn merge_names(a: &mut Vec<String>, b: Vec<String>) {
+    for name in b.into_iter() {
+        if !a.contains(&name) {
+            a.push(name);
+        }
+    }
+}
+
+fn earliest(a: Option<Version>, b: Option<Version>) -> Option<Version> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a < b { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Concatenates two registries without looking for duplicates. `Registry::merge` keeps a single
+///  copy of the definitions that are in both, and fails if they disagree.
 impl Add for Registry {
     type Output = Registry;
 
     fn add(mut self, other: Registry) -> Registry {
         self.groups.extend(other.groups.into_iter());
-        self.enums.extend(other.enums.into_iter());
+        self.types.extend(other.types.into_iter());
+        let offset = self.enum_nss.len();
+        self.enum_nss.extend(other.enum_nss.into_iter());
+        self.enums.extend(other.enums.into_iter().map(|e| Enum { block: e.block + offset, .. e }));
+        self.equivalent_enums.extend(other.equivalent_enums.into_iter().map(|mut e| {
+            e.enm.block += offset;
+            e
+        }));
         self.cmds.extend(other.cmds.into_iter());
         self.features.extend(other.features.into_iter());
         self.extensions.extend(other.extensions.into_iter());
-        self.aliases.extend(other.aliases.into_iter());
+        merge_map(&mut self.aliases, other.aliases);
+        for (ident, other) in other.provenance.into_iter() {
+            let provenance = self.provenance.entry(ident).or_insert_with(Provenance::default);
+            provenance.introduced_in.extend(other.introduced_in.into_iter());
+            provenance.removed_in.extend(other.removed_in.into_iter());
+            provenance.removed_from_core = earliest(provenance.removed_from_core, other.removed_from_core);
+        }
         self
     }
 }
@@ -212,20 +727,70 @@ impl<'a> Iterator for CmdIterator<'a> {
     }
 }
 
+/// A `<group>` of enums, like `PrimitiveType`, that parameters name in their `group` attribute.
 pub struct Group {
     pub name: String,
+    /// The identifiers of the enums of the group, without prefix. Filtering keeps all of them,
+    ///  even the ones that are not in the registry anymore.
     pub enums: Vec<String>,
 }
 
+/// A type defined in the `<types>` section of the registry.
+pub struct Type {
+    pub name: String,
+    /// The API this definition is specific to, if any. The registry can define a type
+    ///  differently for each API, for example `GLfixed` for `gles1`.
+    pub api: Option<String>,
+    /// The name of another type (or header) that must be defined for this one to be valid.
+    pub requires: Option<String>,
+    pub comment: Option<String>,
+    pub def: TypeDef,
+}
+
+/// The C definition of a `Type`.
+#[derive(PartialEq)]
+pub enum TypeDef {
+    /// A placeholder for a type defined outside of the registry, in a platform or Khronos header.
+    External,
+    /// `typedef <ty> <name>;`, where `ty` is a C type such as `unsigned int` or `void *`.
+    Alias(String),
+    /// `typedef <ret> (*<name>)(<params>);`
+    FnPtr {
+        ret: String,
+        params: Vec<Binding>,
+    },
+    /// `struct <name> { ... };` or `typedef struct { ... } <name>;`. Array fields keep their
+    ///  dimension in the type, for example `char [80]`.
+    Struct(Vec<Binding>),
+    /// A struct that is only ever used through pointers, like `struct _cl_context;`.
+    Opaque,
+    /// `DECLARE_HANDLE(<name>);` from the Windows headers.
+    Handle,
+    /// A definition that can't be represented in Rust directly, like preprocessor blocks and
+    ///  unions. Contains the original C code.
+    Unsupported(String),
+}
+
+/// The attributes of an `<enums>` block, shared by all the enums it defines.
+#[derive(Clone)]
 pub struct EnumNs {
     pub namespace: String,
     pub group: Option<String>,
+    /// `bitmask` if the values of the block are meant to be combined with `|`.
     pub ty: Option<String>,
+    /// The range of values reserved for the block, like `0x8000` to `0x80BF`.
     pub start: Option<String>,
     pub end: Option<String>,
+    /// The vendor the range of values is reserved for.
     pub vendor: Option<String>,
     pub comment: Option<String>,
-    pub defs: Vec<Enum>,
+}
+
+impl EnumNs {
+    /// Returns true if the values of the block are bit flags.
+    pub fn is_bitmask(&self) -> bool {
+        self.ty.as_ref().map_or(false, |ty| ty == "bitmask")
+    }
 }
 
 pub struct Enum {
@@ -233,6 +798,19 @@ pub struct Enum {
     pub value: String,
     pub alias: Option<String>,
     pub ty: Option<String>,
+    /// The API this definition is specific to, if any. The registry defines some enums twice,
+    ///  with a different value for each API.
+    pub api: Option<String>,
+    /// The index in `Registry::enum_nss` of the `<enums>` block that defines the enum.
+    pub block: usize,
+}
+
+/// An enum that a filtered registry doesn't require, but that has the same value as one it does
+///  according to the `alias` attributes, like `CLIP_PLANE0` for `CLIP_DISTANCE0`.
+pub struct EquivalentEnum {
+    pub enm: Enum,
+    /// The identifier of the required enum.
+    pub equivalent_to: String,
 }
 
 pub struct CmdNs {
@@ -240,10 +818,84 @@ pub struct CmdNs {
     pub defs: Vec<Cmd>,
 }
 
+#[derive(PartialEq)]
 pub struct Binding {
     pub ident: String,
     pub ty: String,
     pub group: Option<String>,
+    /// The number of elements pointed to by the parameter, if it is an array.
+    pub len: Option<Len>,
+    /// The kind of object named by the parameter, like `buffer` or `texture`.
+    pub class: Option<String>,
+    /// The `kind` attribute of the parameter.
+    pub kind: Option<String>,
+}
+
+/// The length of an array parameter, from the `len` attribute of a `<param>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Len {
+    /// A fixed number of elements, like `len="16"`.
+    Constant(u32),
+    /// The value of another parameter, times a constant factor. For example `len="count*4"` is
+    ///  `Param { name: "count", factor: 4 }`.
+    Param {
+        name: String,
+        factor: u32,
+    },
+    /// A length that is computed from the values of the given parameters in a way that is not
+    ///  described by the registry, like `len="COMPSIZE(format,type)"`.
+    CompSize(Vec<String>),
+    /// A null-terminated string or list.
+    NullTerminated,
+    /// Any other expression, kept as-is.
+    Other(String),
+}
+
+impl Len {
+    /// Parses the value of a `len` attribute.
+    pub fn parse(len: &str) -> Len {
+        let len = len.trim();
+        if len == "null-terminated" {
+            return Len::NullTerminated;
+        }
+        if len.starts_with("COMPSIZE(") && len.ends_with(")") {
+            let args = &len["COMPSIZE(".len() .. len.len() - 1];
+            return Len::CompSize(args.split(',')
+                                     .map(|a| a.trim().to_string())
+                                     .filter(|a| !a.is_empty())
+                                     .collect());
+        }
+        if let Ok(n) = len.parse() {
+            return Len::Constant(n);
+        }
+
+        let (name, factor) = match len.find('*') {
+            Some(pos) => match len[pos + 1 ..].trim().parse() {
+                Ok(factor) => (len[..pos].trim(), factor),
+                Err(_) => return Len::Other(len.to_string()),
+            },
+            None => (len, 1),
+        };
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            Len::Param { name: name.to_string(), factor: factor }
+        } else {
+            Len::Other(len.to_string())
+        }
+    }
+}
+
+/// Formats the length the way the registry writes it, so that `Len::parse` gives it back.
+impl fmt::Display for Len {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Len::Constant(n) => write!(fmt, "{}", n),
+            Len::Param { ref name, factor: 1 } => write!(fmt, "{}", name),
+            Len::Param { ref name, factor } => write!(fmt, "{}*{}", name, factor),
+            Len::CompSize(ref args) => write!(fmt, "COMPSIZE({})", args.join(",")),
+            Len::NullTerminated => write!(fmt, "null-terminated"),
+            Len::Other(ref len) => write!(fmt, "{}", len),
+        }
+    }
 }
 
 pub struct Cmd {
@@ -254,21 +906,37 @@ pub struct Cmd {
     /// Unused by the built-in generators.
     pub is_safe: bool,
     pub alias: Option<String>,
+    /// The command that takes the same arguments as an array, without prefix, like `Color3fv`
+    ///  for `Color3f`.
     pub vecequiv: Option<String>,
     pub glx: Option<GlxOpcode>,
 }
 
+impl Cmd {
+    /// Returns the C prototype of the command, like `void Clear(GLbitfield)`, without the names
+    ///  of the parameters.
+    pub fn signature(&self) -> String {
+        format!("{} {}({})", self.proto.ty.trim(), self.proto.ident,
+                self.params.iter().map(|p| p.ty.trim()).collect::<Vec<_>>().join(", "))
+    }
+}
+
 #[derive(Clone)]
 pub struct Feature {
     pub api: String,
     pub name: String,
-    pub number: String,
+    pub number: Version,
     pub requires: Vec<Require>,
     pub removes: Vec<Remove>,
 }
 
 #[derive(Clone)]
 pub struct Require {
+    /// The API this block applies to, or `None` if it applies to all the APIs of the feature or
+    ///  extension.
+    pub api: Option<String>,
+    /// The profile this block applies to, or `None` if it applies to all profiles.
+    pub profile: Option<String>,
     pub comment: Option<String>,
     /// A reference to the earlier types, by name
     pub enums: Vec<String>,
@@ -278,9 +946,11 @@ pub struct Require {
 
 #[derive(Clone)]
 pub struct Remove {
-    // always core, for now
-    pub profile: String,
-    pub comment: String,
+    /// The API this block applies to, or `None` if it applies to all the APIs of the feature.
+    pub api: Option<String>,
+    /// The profile this block applies to, or `None` if it applies to all profiles.
+    pub profile: Option<String>,
+    pub comment: Option<String>,
     /// A reference to the earlier types, by name
     pub enums: Vec<String>,
     /// A reference to the earlier types, by name
@@ -295,6 +965,116 @@ pub struct Extension {
     pub requires: Vec<Require>,
 }
 
+impl Extension {
+    /// Returns the vendor of the extension, for example `ARB` for `GL_ARB_debug_output`.
+    pub fn vendor(&self) -> Option<&str> {
+        self.name.split('_').nth(1)
+    }
+
+    /// Returns true if the extension can be used with the given API and profile.
+    ///
+    /// The registry marks the extensions that are available in the core profile of OpenGL with
+    ///  the `glcore` pseudo-API.
+    pub fn is_supported(&self, api: &str, profile: Option<Profile>) -> bool {
+        if api == "gl" && profile == Some(Profile::Core) {
+            self.supported.iter().any(|x| x == "glcore")
+        } else {
+            self.supported.iter().any(|x| x == api)
+        }
+    }
+}
+
+/// Selects the extensions to include in the bindings.
+///
+/// Extensions are selected by their full name, like `GL_ARB_debug_output`. Patterns can contain
+///  `*`, which matches any sequence of characters, and `?`, which matches any single character.
+///  The excludes are applied last and take precedence over everything else.
+#[derive(Clone, Debug, Default)]
+pub struct ExtensionSelector {
+    /// Names or patterns of the extensions to include. Naming an extension that is not in the
+    ///  registry, or that does not support the API, is an error. Patterns only match the
+    ///  extensions that are supported.
+    pub names: Vec<String>,
+    /// Vendors whose supported extensions are included, like `"ARB"` or `"KHR"`.
+    pub vendors: Vec<String>,
+    /// Include every extension that is supported by the API and profile.
+    pub all_supported: bool,
+    /// Names or patterns of the extensions to leave out.
+    pub exclude: Vec<String>,
+    /// Vendors whose extensions are left out, like `"NV"`.
+    pub exclude_vendors: Vec<String>,
+}
+
+impl From<Vec<String>> for ExtensionSelector {
+    fn from(names: Vec<String>) -> ExtensionSelector {
+        ExtensionSelector { names: names, .. ExtensionSelector::default() }
+    }
+}
+
+impl ExtensionSelector {
+    /// Returns the selected extensions, in the order of the registry.
+    fn select<'a>(&self, exts: &'a [Extension], api: &str, profile: Option<Profile>)
+                  -> Result<Vec<&'a Extension>, Error>
+    {
+        // an exact name must refer to a known extension, even if it doesn't support the profile
+        for name in self.names.iter().filter(|name| !is_pattern(name)) {
+            match exts.iter().find(|ext| &ext.name == name) {
+                Some(ext) if ext.supported.iter().any(|x| x == api) => (),
+                Some(ext) => return Err(Error::UnsupportedExtension {
+                    name: ext.name.clone(),
+                    api: api.to_string(),
+                }),
+                None => return Err(Error::UnknownExtension(name.clone())),
+            }
+        }
+
+        Ok(exts.iter().filter(|ext| {
+            let named = self.names.iter().any(|name| &ext.name == name);
+            let matched = ext.is_supported(api, profile) && (
+                self.all_supported ||
+                self.names.iter().any(|pattern| is_pattern(pattern) && glob_match(pattern, &ext.name)) ||
+                ext.vendor().map_or(false, |v| self.vendors.iter().any(|x| x == v))
+            );
+            let excluded =
+                self.exclude.iter().any(|pattern| glob_match(pattern, &ext.name)) ||
+                ext.vendor().map_or(false, |v| self.exclude_vendors.iter().any(|x| x == v));
+
+            (named || matched) && !excluded
+        }).collect())
+    }
+}
+
+fn is_pattern(name: &str) -> bool {
+    name.contains('*') || name.contains('?')
+}
+
+/// Matches a name against a pattern containing `*` and `?` wildcards.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // position in the pattern of the last `*`, and position in the name it was tried at
+    let mut star = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // let the last `*` match one more character
+            star = Some((star_p, star_n + 1));
+            p = star_p + 1;
+            n = star_n + 1;
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 pub struct GlxOpcode {
     pub ty: String,
     pub opcode: String,
@@ -306,233 +1086,561 @@ struct RegistryBuilder<R: io::Read> {
     pub ns: Ns,
     pub filter: Option<Filter>,
     pub port: RefCell<xml::reader::EventReader<R>>,
+    /// The names of the elements enclosing the current position, used for error reporting
+    pub path: RefCell<Vec<String>>,
 }
 
 pub struct Filter {
     pub fallbacks: Fallbacks,
-    pub extensions: Vec<String>,
-    pub profile: String,
-    pub version: String,
+    pub extensions: ExtensionSelector,
+    /// The profile to generate. Required for OpenGL, and ignored by the APIs that don't have
+    ///  profiles.
+    pub profile: Option<Profile>,
+    /// The version to generate, or `None` for the latest version of the API in the registry.
+    pub version: Option<Version>,
     pub api: String,
 }
 
+impl Filter {
+    /// Returns true if a `<require>` or `<remove>` block with the given `api` and `profile`
+    ///  attributes applies to the bindings being generated, given the profile in use (`None` if
+    ///  the API doesn't have profiles).
+    fn matches(&self, in_use: Option<Profile>, api: &Option<String>, profile: &Option<String>) -> bool {
+        api.as_ref().map_or(true, |api| api == &self.api) &&
+            match (in_use, profile.as_ref()) {
+                (Some(in_use), Some(profile)) => profile == &in_use.to_string(),
+                _ => true,
+            }
+    }
+}
+
+fn enums_by_ident<'a, I>(enums: I) -> HashMap<&'a str, &'a Enum> where I: Iterator<Item = &'a Enum> {
+    let mut by_ident = HashMap::new();
+    for enm in enums {
+        by_ident.entry(&enm.ident[..]).or_insert(enm);
+    }
+    by_ident
+}
+
+/// Returns the enums met by following the `alias` attributes from `enm`, starting with `enm`
+///  itself. The chain stops at an enum without alias, or whose alias isn't in `by_ident`.
+///
+/// Fails if two consecutive enums have different values, or if the chain loops.
+fn alias_chain<'a>(by_ident: &HashMap<&str, &'a Enum>, enm: &'a Enum) -> Result<Vec<&'a Enum>, Error> {
+    let mut chain = vec![enm];
+    loop {
+        let current = chain[chain.len() - 1];
+        let next = match current.alias.as_ref().and_then(|alias| by_ident.get(&alias[..])) {
+            Some(next) => *next,
+            None => return Ok(chain),
+        };
+        if chain.iter().any(|e| e.ident == next.ident) {
+            return Err(Error::EnumAliasCycle(enm.ident.clone()));
+        }
+        if !same_enum_value(&current.value, &next.value) {
+            return Err(Error::ConflictingEnumAlias {
+                name: current.ident.clone(),
+                alias: next.ident.clone(),
+                values: vec![current.value.clone(), next.value.clone()],
+            });
+        }
+        chain.push(next);
+    }
+}
+
+/// Returns the number that an enum value stands for, ignoring casts like `((EGLSync)0)` and
+///  suffixes like `ull`, or `None` if the value isn't a number.
+fn enum_number(value: &str) -> Option<u64> {
+    let value = match value.rfind(')') {
+        Some(end) if value.starts_with("((") => value[.. end].rsplit(')').next().unwrap(),
+        _ => value,
+    };
+    let value = value.trim_right_matches(|c| c == 'u' || c == 'l' || c == 'U' || c == 'L');
+    if value.starts_with("0x") || value.starts_with("0X") {
+        u64::from_str_radix(&value[2 ..], 16).ok()
+    } else if value.starts_with("-") {
+        value.parse::<i64>().ok().map(|n| n as u64)
+    } else {
+        value.parse().ok()
+    }
+}
+
+fn same_enum_value(a: &str, b: &str) -> bool {
+    a == b || enum_number(a).map_or(false, |n| Some(n) == enum_number(b))
+}
+
+/// Returns the enums of `others` that end up at the same enum as one of `enums` when following
+///  their `alias` attributes.
+///
+/// The registry has a few aliases whose values disagree. They are reported and not followed.
+fn equivalent_enums(enums: &[Enum], others: Vec<Enum>) -> Vec<EquivalentEnum> {
+    let mut equivalents: HashMap<String, String> = HashMap::new();
+    {
+        let by_ident = enums_by_ident(enums.iter().chain(others.iter()));
+        let last = |enm: &Enum| -> Option<String> {
+            match alias_chain(&by_ident, enm) {
+                Ok(chain) => Some(chain[chain.len() - 1].ident.clone()),
+                Err(err) => {
+                    warn!("Ignoring the alias of `{}`: {}", enm.ident, err);
+                    None
+                }
+            }
+        };
+
+        let mut required: HashMap<String, &str> = HashMap::new();
+        for enm in enums.iter() {
+            let last = last(enm).unwrap_or_else(|| enm.ident.clone());
+            required.entry(last).or_insert(&enm.ident);
+        }
+        for enm in others.iter() {
+            if let Some(ident) = last(enm).and_then(|last| required.get(&last)) {
+                equivalents.insert(enm.ident.clone(), ident.to_string());
+            }
+        }
+    }
+
+    others.into_iter().filter_map(|enm| {
+        let equivalent_to = equivalents.get(&enm.ident).cloned();
+        equivalent_to.map(|equivalent_to| EquivalentEnum { enm: enm, equivalent_to: equivalent_to })
+    }).collect()
+}
+
+/// Returns the versions of the API that the features define.
+///
+/// A feature can cover several versions, like the single feature of OpenGL ES 1.0 and 1.1. Those
+///  are found from the `VERSION_<major>_<minor>` enums that the feature requires.
+fn feature_versions(feats: &[Feature], api: &str) -> Vec<Version> {
+    let mut versions: Vec<Version> = Vec::new();
+    for f in feats.iter().filter(|f| f.api == api) {
+        let covered = f.requires.iter()
+                                .flat_map(|r| r.enums.iter())
+                                .filter(|name| name.contains("VERSION_"))
+                                .filter_map(|name| {
+                                    let mut parts = name.rsplitn(3, '_');
+                                    let minor = parts.next().and_then(|m| m.parse().ok());
+                                    let major = parts.next().and_then(|m| m.parse().ok());
+                                    match (major, minor) {
+                                        (Some(major), Some(minor)) => Some(Version::new(major, minor)),
+                                        _ => None,
+                                    }
+                                })
+                                .filter(|&v| v > f.number);
+        for version in Some(f.number).into_iter().chain(covered) {
+            if !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+    }
+    versions
+}
+
+/// Returns true if the features of the API have `<require>` or `<remove>` blocks that are
+///  specific to the core or compatibility profile.
+fn has_profiles(feats: &[Feature], api: &str) -> bool {
+    let is_profile = |profile: &Option<String>| {
+        profile.as_ref().map_or(false, |p| p.parse::<Profile>().is_ok())
+    };
+    feats.iter().filter(|f| f.api == api).any(|f| {
+        f.requires.iter().any(|r| is_profile(&r.profile)) ||
+            f.removes.iter().any(|r| is_profile(&r.profile))
+    })
+}
+
 /// A big, ugly, imperative impl with methods that accumulates a Registry struct
 impl<R: io::Read> RegistryBuilder<R> {
-    fn recv(&self) -> XmlEvent {
+    fn recv(&self) -> Result<XmlEvent, Error> {
         for event in self.port.borrow_mut().events() {
             match event {
                 XmlEvent::StartDocument{..} => (),
                 XmlEvent::Comment(_) => (),
                 XmlEvent::Whitespace(_) => (),
-                XmlEvent::EndDocument => panic!("The end of the document has been reached"),
-                XmlEvent::Error(err) => panic!("XML error: {:?}", err),
-                event => return event,
+                XmlEvent::EndDocument => return Err(self.unexpected(&XmlEvent::EndDocument)),
+                XmlEvent::Error(err) => return Err(Error::Xml {
+                    row: err.row(),
+                    col: err.col(),
+                    msg: err.msg().to_string(),
+                }),
+                event => {
+                    match event {
+                        XmlEvent::StartElement{ref name, ..} =>
+                            self.path.borrow_mut().push(name.local_name.clone()),
+                        XmlEvent::EndElement{..} => { self.path.borrow_mut().pop(); },
+                        _ => (),
+                    }
+                    return Ok(event);
+                }
             }
         }
 
-        unreachable!()
+        Err(self.unexpected(&XmlEvent::EndDocument))
     }
 
-    fn expect_characters(&self) -> String {
-        match self.recv() {
-            XmlEvent::Characters(ref ch) => ch.clone(),
-            msg => panic!("Expected characters, found: {:?}", msg),
+    /// Creates an error for an event that the parser did not expect at the current position.
+    fn unexpected(&self, event: &XmlEvent) -> Error {
+        let mut path = self.path.borrow().clone();
+        let found = match *event {
+            XmlEvent::StartElement{ref name, ..} => {
+                // `recv` has already entered the element, but we want to report its parent
+                path.pop();
+                format!("element <{}>", name.local_name)
+            },
+            XmlEvent::EndElement{ref name} => format!("end of element </{}>", name.local_name),
+            XmlEvent::Characters(ref ch) => format!("text {:?}", ch),
+            XmlEvent::EndDocument => "end of document".to_string(),
+            ref msg => format!("{:?}", msg),
+        };
+        Error::UnexpectedElement {
+            path: path.join("/"),
+            found: found,
         }
     }
 
-    fn expect_start_element(&self, n: &str) -> Vec<OwnedAttribute> {
-        match self.recv() {
+    fn expect_attribute(&self, a: &[OwnedAttribute], name: &str) -> Result<String, Error> {
+        get_attribute(a, name).ok_or_else(|| Error::MissingAttribute {
+            path: self.path.borrow().join("/"),
+            attribute: name.to_string(),
+        })
+    }
+
+    fn expect_characters(&self) -> Result<String, Error> {
+        match try!(self.recv()) {
+            XmlEvent::Characters(ref ch) => Ok(ch.clone()),
+            msg => Err(self.unexpected(&msg)),
+        }
+    }
+
+    fn expect_start_element(&self, n: &str) -> Result<Vec<OwnedAttribute>, Error> {
+        match try!(self.recv()) {
             XmlEvent::StartElement{ref name, ref attributes, ..}
-                if n == name.local_name => attributes.clone(),
-            msg => panic!("Expected <{}>, found: {:?}", n, msg),
+                if n == name.local_name => Ok(attributes.clone()),
+            msg => Err(self.unexpected(&msg)),
         }
     }
 
-    fn expect_end_element(&self, n: &str) {
-        match self.recv() {
-            XmlEvent::EndElement{ref name} if n == name.local_name => (),
-            msg => panic!("Expected </{}>, found: {:?}", n, msg),
+    fn expect_end_element(&self, n: &str) -> Result<(), Error> {
+        match try!(self.recv()) {
+            XmlEvent::EndElement{ref name} if n == name.local_name => Ok(()),
+            msg => Err(self.unexpected(&msg)),
         }
     }
 
-    fn skip_until(&self, event: XmlEvent) {
+    fn skip_until(&self, event: XmlEvent) -> Result<(), Error> {
         loop {
-            match self.recv() {
-                XmlEvent::EndDocument => panic!("Expected {:?}, but reached the end of the document.",
-                                     event),
-                ref msg if *msg == event => break,
+            match try!(self.recv()) {
+                ref msg if *msg == event => return Ok(()),
                 _ => (),
             }
         }
     }
 
-    fn consume_registry(&self) -> Registry {
-        self.expect_start_element("registry");
+    fn consume_registry(&self) -> Result<Registry, Error> {
+        try!(self.expect_start_element("registry"));
         let mut registry = Registry {
             groups: Vec::new(),
+            types: Vec::new(),
+            enum_nss: Vec::new(),
             enums: Vec::new(),
             cmds: Vec::new(),
             features: Vec::new(),
             extensions: Vec::new(),
             aliases: HashMap::new(),
+            equivalent_enums: Vec::new(),
+            provenance: HashMap::new(),
         };
 
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 // ignores
                 XmlEvent::Characters(_) | XmlEvent::Comment(_) => (),
                 XmlEvent::StartElement{ref name, ..}
                     if name.local_name == "comment" =>
-                        self.skip_until(XmlEvent::EndElement { name: name.clone() }),
-                XmlEvent::StartElement{ref name, ..}
-                    if name.local_name == "types" =>
-                        self.skip_until(XmlEvent::EndElement { name: name.clone() }),
+                        try!(self.skip_until(XmlEvent::EndElement { name: name.clone() })),
+
+                // add types
+                XmlEvent::StartElement{ref name, ..} if name.local_name == "types" => {
+                    loop {
+                        match try!(self.recv()) {
+                            XmlEvent::Characters(_) | XmlEvent::Comment(_) => (),
+                            XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "type" => {
+                                registry.types.push(try!(self.consume_type(attributes)));
+                            }
+                            XmlEvent::EndElement{ref name} if name.local_name == "types" => break,
+                            msg => return Err(self.unexpected(&msg)),
+                        }
+                    }
+                }
 
                 // add groups
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "groups" => {
                     loop {
-                        match self.recv() {
+                        match try!(self.recv()) {
                             XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "group" => {
-                                registry.groups.push(
-                                    self.consume_group(get_attribute(attributes, "name").unwrap())
-                                );
+                                let name = try!(self.expect_attribute(attributes, "name"));
+                                registry.groups.push(try!(self.consume_group(name)));
                             }
                             XmlEvent::EndElement{ref name} if name.local_name == "groups" => break,
-                            msg => panic!("Expected </groups>, found: {:?}", msg),
+                            msg => return Err(self.unexpected(&msg)),
                         }
                     }
                 }
 
                 // add enum namespace
-                XmlEvent::StartElement{ref name, ..} if name.local_name == "enums" => {
-                    registry.enums.extend(self.consume_enums().into_iter());
+                XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "enums" => {
+                    registry.enum_nss.push(EnumNs {
+                        namespace: try!(self.expect_attribute(attributes, "namespace")),
+                        group: get_attribute(attributes, "group"),
+                        ty: get_attribute(attributes, "type"),
+                        start: get_attribute(attributes, "start"),
+                        end: get_attribute(attributes, "end"),
+                        vendor: get_attribute(attributes, "vendor"),
+                        comment: get_attribute(attributes, "comment"),
+                    });
+                    let block = registry.enum_nss.len() - 1;
+                    registry.enums.extend(try!(self.consume_enums(block)).into_iter());
                 }
 
                 // add command namespace
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "commands" => {
-                    let (cmds, aliases) = self.consume_cmds();
+                    let (cmds, aliases) = try!(self.consume_cmds());
                     registry.cmds.extend(cmds.into_iter());
                     merge_map(&mut registry.aliases, aliases);
                 }
 
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "feature" => {
                     debug!("Parsing feature: {:?}", attributes);
-                    registry.features.push(FromXML::convert(self, &attributes));
+                    registry.features.push(try!(FromXML::convert(self, &attributes)));
                 }
 
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "extensions" => {
                     loop {
-                        match self.recv() {
+                        match try!(self.recv()) {
                             XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "extension" => {
-                                registry.extensions.push(FromXML::convert(self, &attributes));
+                                registry.extensions.push(try!(FromXML::convert(self, &attributes)));
                             }
                             XmlEvent::EndElement{ref name} if name.local_name == "extensions" => break,
-                            msg => panic!("Unexpected message {:?}", msg),
+                            msg => return Err(self.unexpected(&msg)),
                         }
                     }
                 }
 
                 // finished building the registry
-                XmlEvent::EndElement{ref name} if name.local_name == "registry" => break,
+                XmlEvent::EndElement{ref name} if name.local_name == "registry" => {
+                    registry.aliases = complete_aliases(registry.aliases);
+                    break;
+                }
 
                 // error handling
-                msg => panic!("Expected </registry>, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
 
         match self.filter {
             Some(ref filter) => {
                 let Registry {
-                    groups, enums, cmds, aliases, features: feats, extensions: exts,
+                    groups, types, enum_nss, enums, cmds, aliases, features: feats, extensions: exts, ..
                 } = registry;
 
                 let mut desired_enums: HashSet<String> = HashSet::new();
                 let mut desired_cmds: HashSet<String> = HashSet::new();
 
-                // find the features we want
-                let mut found_feat = false;
-                for f in feats.iter() {
-                    // XXX: verify that the string comparison with <= actually works as desired
-                    if f.api == filter.api && f.number <= filter.version {
-                        for req in f.requires.iter() {
-                            desired_enums.extend(req.enums.iter().map(|x| x.clone()));
-                            desired_cmds.extend(req.commands.iter().map(|x| x.clone()));
+                // the versions of the requested API that the registry knows about
+                let available = feature_versions(&feats, &filter.api);
+
+                let version = match filter.version {
+                    Some(version) if available.contains(&version) => version,
+                    Some(version) => return Err(Error::UnknownVersion {
+                        api: filter.api.clone(),
+                        version: version,
+                        available: available,
+                    }),
+                    None => match available.iter().max() {
+                        Some(&version) => version,
+                        None => return Err(Error::UnknownApi(filter.api.clone())),
+                    },
+                };
+
+                let profile = if has_profiles(&feats, &filter.api) {
+                    match filter.profile {
+                        Some(profile) => Some(profile),
+                        None => return Err(Error::ProfileRequired(filter.api.clone())),
+                    }
+                } else {
+                    if let Some(profile) = filter.profile {
+                        debug!("Ignoring the {} profile, {} doesn't have profiles", profile, filter.api);
+                    }
+                    None
+                };
+
+                // the commands and enums that the core profile removes in versions or profiles that
+                // aren't selected, so that generators can point out what a later version drops.
+                // The features are walked in order, since a later version of the core profile can
+                // bring back what an earlier one removed
+                let mut core_removals: Vec<(Version, String)> = Vec::new();
+                let mut core_feats: Vec<&Feature> = feats.iter()
+                    .filter(|f| f.api == filter.api && profile.is_some())
+                    .collect();
+                core_feats.sort_by_key(|f| f.number);
+                for f in core_feats {
+                    let core = Some(Profile::Core);
+                    if f.number <= version {
+                        for req in f.requires.iter().filter(|r| filter.matches(core, &r.api, &r.profile)) {
+                            core_removals.retain(|&(_, ref name)| {
+                                !req.enums.contains(name) && !req.commands.contains(name)
+                            });
+                        }
+                        if profile == Some(Profile::Core) {
+                            continue;
                         }
                     }
-                    if f.number == filter.version {
-                        found_feat = true;
+                    for rem in f.removes.iter().filter(|r| filter.matches(core, &r.api, &r.profile)) {
+                        if rem.profile.is_some() {
+                            core_removals.extend(rem.enums.iter().chain(rem.commands.iter())
+                                                    .map(|name| (f.number, name.clone())));
+                        }
                     }
                 }
 
-                // remove the things that should be removed
+                // find the features we want, in order, since a feature can bring back things that
+                // an earlier one removed
+                let mut feats: Vec<Feature> = feats.into_iter()
+                    .filter(|f| f.api == filter.api && f.number <= version)
+                    .collect();
+                feats.sort_by_key(|f| f.number);
+
+                let mut provenance: HashMap<String, Provenance> = HashMap::new();
+
                 for f in feats.iter() {
-                    // XXX: verify that the string comparison with <= actually works as desired
-                    if f.api == filter.api && f.number <= filter.version {
-                        for rem in f.removes.iter() {
-                            if rem.profile == filter.profile {
-                                for enm in rem.enums.iter() {
-                                    debug!("Removing {}", enm);
-                                    desired_enums.remove(enm);
-                                }
-                                for cmd in rem.commands.iter() {
-                                    debug!("Removing {}", cmd);
-                                    desired_cmds.remove(cmd);
-                                }
-                            }
+                    for req in f.requires.iter().filter(|r| filter.matches(profile, &r.api, &r.profile)) {
+                        desired_enums.extend(req.enums.iter().map(|x| x.clone()));
+                        desired_cmds.extend(req.commands.iter().map(|x| x.clone()));
+                        self.record_provenance(&mut provenance, req.enums.iter().chain(req.commands.iter()),
+                                               |p| &mut p.introduced_in, &f.name);
+                    }
+
+                    // remove the things that should be removed
+                    for rem in f.removes.iter().filter(|r| filter.matches(profile, &r.api, &r.profile)) {
+                        for enm in rem.enums.iter() {
+                            debug!("Removing {}", enm);
+                            desired_enums.remove(enm);
+                        }
+                        for cmd in rem.commands.iter() {
+                            debug!("Removing {}", cmd);
+                            desired_cmds.remove(cmd);
                         }
+                        self.record_provenance(&mut provenance, rem.enums.iter().chain(rem.commands.iter()),
+                                               |p| &mut p.removed_in, &f.name);
                     }
                 }
 
-                if !found_feat {
-                    panic!("Did not find version {} in the registry", filter.version);
+                let exts: Vec<Extension> = try!(filter.extensions.select(&exts, &filter.api, profile))
+                    .into_iter().cloned().collect();
+                for ext in exts.iter() {
+                    for req in ext.requires.iter().filter(|r| filter.matches(profile, &r.api, &r.profile)) {
+                        desired_enums.extend(req.enums.iter().map(|x| x.clone()));
+                        desired_cmds.extend(req.commands.iter().map(|x| x.clone()));
+                        self.record_provenance(&mut provenance, req.enums.iter().chain(req.commands.iter()),
+                                               |p| &mut p.introduced_in, &ext.name);
+                    }
                 }
 
-                for ext in exts.iter() {
-                    if filter.extensions.iter().any(|x| x == &ext.name) {
-                        if !ext.supported.iter().any(|x| x == &filter.api) {
-                            panic!("Requested {}, which doesn't support the {} API", ext.name, filter.api);
-                        }
-                        for req in ext.requires.iter() {
-                            desired_enums.extend(req.enums.iter().map(|x| x.clone()));
-                            desired_cmds.extend(req.commands.iter().map(|x| x.clone()));
-                        }
+                for &(number, ref name) in core_removals.iter() {
+                    if !desired_enums.contains(name) && !desired_cmds.contains(name) {
+                        continue;
+                    }
+                    let ident = trim_cmd_prefix(trim_enum_prefix(name, self.ns), self.ns);
+                    let entry = provenance.entry(ident.to_string()).or_insert_with(Provenance::default);
+                    if entry.removed_from_core.is_none() {
+                        entry.removed_from_core = Some(number);
                     }
                 }
 
-                let aliases = if let &Filter { fallbacks: Fallbacks::None, ..} = filter { HashMap::new() } else { aliases };
+                let aliases = match filter.fallbacks {
+                    Fallbacks::All => aliases,
+                    Fallbacks::None => HashMap::new(),
+                    Fallbacks::Policy(ref policy) => policy.apply(aliases),
+                };
+
+                // keep the API-specific definition of a type if there is one, and the generic
+                // definition otherwise
+                let specific: HashSet<String> = types.iter()
+                    .filter(|t| t.api.as_ref() == Some(&filter.api))
+                    .map(|t| t.name.clone())
+                    .collect();
+                let types = types.into_iter().filter(|t| {
+                    match t.api {
+                        Some(ref api) => api == &filter.api,
+                        None => !specific.contains(&t.name),
+                    }
+                }).collect();
+
+                // same for the enums, but two definitions of an enum must not disagree
+                let specific: HashSet<String> = enums.iter()
+                    .filter(|e| e.api.as_ref() == Some(&filter.api))
+                    .map(|e| e.ident.clone())
+                    .collect();
+                let enums: Vec<Enum> = enums.into_iter().filter(|e| {
+                    match e.api {
+                        Some(ref api) => api == &filter.api,
+                        None => !specific.contains(&e.ident),
+                    }
+                }).collect();
+
+                let enum_prefix = self.ns.info().enum_prefix;
+                let (enums, others): (Vec<Enum>, Vec<Enum>) = enums.into_iter().partition(|e| {
+                    desired_enums.contains(&(enum_prefix.to_string() + &e.ident))
+                });
+                for (i, a) in enums.iter().enumerate() {
+                    if let Some(b) = enums[i + 1 ..].iter().find(|b| b.ident == a.ident && b.value != a.value) {
+                        return Err(Error::ConflictingEnum {
+                            name: a.ident.clone(),
+                            values: vec![a.value.clone(), b.value.clone()],
+                        });
+                    }
+                }
+                let equivalent_enums = equivalent_enums(&enums, others);
 
-                Registry {
+                Ok(Registry {
                     groups: groups,
-                    enums: enums.into_iter().filter(|e| {
-                            desired_enums.contains(&("GL_".to_string() + &e.ident)) ||
-                            desired_enums.contains(&("WGL_".to_string() + &e.ident)) ||
-                            desired_enums.contains(&("GLX_".to_string() + &e.ident)) ||
-                            desired_enums.contains(&("EGL_".to_string() + &e.ident))
-                        }).collect::<Vec<Enum>>(),
+                    types: types,
+                    enum_nss: enum_nss,
+                    enums: enums,
+                    equivalent_enums: equivalent_enums,
                     cmds: cmds.into_iter().filter(|c| {
-                            desired_cmds.contains(&("gl".to_string() + &c.proto.ident)) ||
-                            desired_cmds.contains(&("wgl".to_string() + &c.proto.ident)) ||
-                            desired_cmds.contains(&("glX".to_string() + &c.proto.ident)) ||
-                            desired_cmds.contains(&("egl".to_string() + &c.proto.ident))
+                            desired_cmds.contains(&(self.ns.info().cmd_prefix.to_string() + &c.proto.ident))
                         }).collect::<Vec<Cmd>>(),
-                    // these aren't important after this step
-                    features: Vec::new(),
-                    extensions: Vec::new(),
+                    // the features and extensions that have been selected
+                    features: feats,
+                    extensions: exts,
                     aliases: aliases,
-                }
+                    provenance: provenance,
+                })
             },
-            None => registry
+            None => Ok(registry)
         }
     }
 
-    fn consume_two<'a, T: FromXML, U: FromXML>(&self, one: &'a str, two: &'a str, end: &'a str) -> (Vec<T>, Vec<U>) {
+    /// Adds `source` to the provenance of each of the named enums and commands. The names still
+    ///  have their prefix, like in `<require>` blocks.
+    fn record_provenance<'a, I, F>(&self, provenance: &mut HashMap<String, Provenance>, names: I,
+                                   field: F, source: &str)
+        where I: Iterator<Item = &'a String>, F: Fn(&mut Provenance) -> &mut Vec<String>
+    {
+        for name in names {
+            let ident = trim_cmd_prefix(trim_enum_prefix(name, self.ns), self.ns);
+            let entry = provenance.entry(ident.to_string()).or_insert_with(Provenance::default);
+            field(entry).push(source.to_string());
+        }
+    }
+
+    fn consume_two<'a, T: FromXML, U: FromXML>(&self, one: &'a str, two: &'a str, end: &'a str) -> Result<(Vec<T>, Vec<U>), Error> {
         debug!("consume_two: looking for {} and {} until {}", one, two, end);
 
         let mut ones = Vec::new();
         let mut twos = Vec::new();
 
         loop {
-            match self.recv() {
+            let event = try!(self.recv());
+            match event {
                 XmlEvent::StartElement{ref name, ref attributes, ..} => {
                     debug!("Found start element <{:?} {:?}>", name, attributes);
                     debug!("one and two are {} and {}", one, two);
@@ -540,7 +1648,7 @@ impl<R: io::Read> RegistryBuilder<R> {
                     let n = name.clone();
 
                     if one == n.local_name {
-                        ones.push(FromXML::convert(self, &attributes));
+                        ones.push(try!(FromXML::convert(self, &attributes)));
                     } else if "type" == n.local_name {
                         // XXX: GL1.1 contains types, which we never care about anyway.
                         // Make sure consume_two doesn't get used for things which *do*
@@ -548,9 +1656,9 @@ impl<R: io::Read> RegistryBuilder<R> {
                         warn!("Ignoring type!");
                         continue;
                     } else if two == n.local_name {
-                        twos.push(FromXML::convert(self, &attributes));
+                        twos.push(try!(FromXML::convert(self, &attributes)));
                     } else {
-                        panic!("Unexpected element: <{:?} {:?}>", n, &attributes);
+                        return Err(self.unexpected(&event));
                     }
                 },
                 XmlEvent::EndElement{ref name} => {
@@ -565,72 +1673,126 @@ impl<R: io::Read> RegistryBuilder<R> {
                         warn!("Ignoring type!");
                         continue;
                     } else if end == name.local_name {
-                        return (ones, twos);
+                        return Ok((ones, twos));
                     } else {
-                        panic!("Unexpected end element {:?}", name.local_name);
+                        return Err(self.unexpected(&event));
                     }
                 },
-                msg => panic!("Unexpected message {:?}", msg) }
+                _ => return Err(self.unexpected(&event)),
+            }
         }
     }
 
-    fn consume_group(&self, name: String) -> Group {
+    fn consume_group(&self, name: String) -> Result<Group, Error> {
         let mut enms = Vec::new();
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "enum" => {
-                    enms.push(get_attribute(&attributes, "name").unwrap());
-                    self.expect_end_element("enum");
+                    let enm = try!(self.expect_attribute(&attributes, "name"));
+                    enms.push(trim_enum_prefix(&enm, self.ns).to_string());
+                    try!(self.expect_end_element("enum"));
                 }
                 XmlEvent::EndElement{ref name} if name.local_name == "group" => break,
-                msg => panic!("Expected </group>, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
-        Group {
+        Ok(Group {
             name: name,
             enums: enms,
+        })
+    }
+
+    fn consume_type(&self, attributes: &[OwnedAttribute]) -> Result<Type, Error> {
+        // the C code of the definition, split around the `<name>` element
+        let mut prefix = String::new();
+        let mut ident = None;
+        let mut suffix = String::new();
+        loop {
+            match try!(self.recv()) {
+                XmlEvent::Characters(ch) | XmlEvent::CData(ch) => {
+                    if ident.is_none() { prefix.push_str(&ch) } else { suffix.push_str(&ch) }
+                }
+                XmlEvent::StartElement{ref name, ..} if name.local_name == "name" => {
+                    ident = Some(try!(self.expect_characters()));
+                    try!(self.expect_end_element("name"));
+                }
+                XmlEvent::StartElement{ref name, ..} if name.local_name == "apientry" => {
+                    try!(self.expect_end_element("apientry"));
+                }
+                XmlEvent::EndElement{ref name} if name.local_name == "type" => break,
+                msg => return Err(self.unexpected(&msg)),
+            }
         }
+
+        let (name, def) = match ident {
+            // `<name>struct _cl_context</name>;`
+            Some(ref ident) if ident.starts_with("struct ") =>
+                (ident["struct ".len()..].trim().to_string(), TypeDef::Opaque),
+            Some(ident) => {
+                let def = parse_type_def(prefix.trim(), suffix.trim());
+                (ident, def)
+            }
+            None => {
+                let name = try!(self.expect_attribute(attributes, "name"));
+                let def = if prefix.trim().is_empty() {
+                    TypeDef::External
+                } else {
+                    TypeDef::Unsupported(prefix.trim().to_string())
+                };
+                (name, def)
+            }
+        };
+
+        Ok(Type {
+            name: name,
+            api: get_attribute(attributes, "api"),
+            requires: get_attribute(attributes, "requires"),
+            comment: get_attribute(attributes, "comment"),
+            def: def,
+        })
     }
 
-    fn consume_enums(&self) -> Vec<Enum> {
+    fn consume_enums(&self, block: usize) -> Result<Vec<Enum>, Error> {
         let mut enums = Vec::new();
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 // ignores
                 XmlEvent::Characters(_) | XmlEvent::Comment(_) => (),
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "unused" =>
-                    self.skip_until(XmlEvent::EndElement{name: name.clone()}),
+                    try!(self.skip_until(XmlEvent::EndElement{name: name.clone()})),
 
                 // add enum definition
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "enum" => {
                     enums.push(
                         Enum {
-                            ident:  trim_enum_prefix(&get_attribute(&attributes, "name").unwrap(), self.ns).to_string(),
-                            value:  get_attribute(&attributes, "value").unwrap(),
-                            alias:  get_attribute(&attributes, "alias"),
+                            ident:  trim_enum_prefix(&try!(self.expect_attribute(&attributes, "name")), self.ns).to_string(),
+                            value:  try!(self.expect_attribute(&attributes, "value")),
+                            alias:  get_attribute(&attributes, "alias").map(|a| trim_enum_prefix(&a, self.ns).to_string()),
                             ty:     get_attribute(&attributes, "type"),
+                            api:    get_attribute(&attributes, "api"),
+                            block:  block,
                         }
                     );
-                    self.expect_end_element("enum");
+                    try!(self.expect_end_element("enum"));
                 }
 
                 // finished building the namespace
                 XmlEvent::EndElement{ref name} if name.local_name == "enums" => break,
                 // error handling
-                msg => panic!("Expected </enums>, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
-        enums
+        Ok(enums)
     }
 
-    fn consume_cmds(&self) -> (Vec<Cmd>, HashMap<String, Vec<String>>) {
+    fn consume_cmds(&self) -> Result<(Vec<Cmd>, HashMap<String, Vec<String>>), Error> {
         let mut cmds = Vec::new();
         let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 // add command definition
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "command" => {
-                    let new = self.consume_cmd();
+                    let new = try!(self.consume_cmd());
                     match new.alias {
                         Some(ref v) => {
                             match aliases.entry(v.clone()) {
@@ -645,16 +1807,16 @@ impl<R: io::Read> RegistryBuilder<R> {
                 // finished building the namespace
                 XmlEvent::EndElement{ref name} if name.local_name == "commands" => break,
                 // error handling
-                msg => panic!("Expected </commands>, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
-        (cmds, aliases)
+        Ok((cmds, aliases))
     }
 
-    fn consume_cmd(&self) -> Cmd {
+    fn consume_cmd(&self) -> Result<Cmd, Error> {
         // consume command prototype
-        let proto_attr = self.expect_start_element("proto");
-        let mut proto = self.consume_binding("proto", get_attribute(&proto_attr, "group"));
+        let proto_attr = try!(self.expect_start_element("proto"));
+        let mut proto = try!(self.consume_binding("proto", &proto_attr));
         proto.ident = trim_cmd_prefix(&proto.ident, self.ns).to_string();
 
         let mut params = Vec::new();
@@ -662,164 +1824,259 @@ impl<R: io::Read> RegistryBuilder<R> {
         let mut vecequiv = None;
         let mut glx = None;
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "param" => {
                     params.push(
-                        self.consume_binding("param", get_attribute(&attributes, "group"))
+                        try!(self.consume_binding("param", &attributes))
                     );
                 }
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "alias" => {
                     alias = get_attribute(&attributes, "name");
                     alias = alias.map(|t| trim_cmd_prefix(&t, self.ns).to_string());
-                    self.expect_end_element("alias");
+                    try!(self.expect_end_element("alias"));
                 }
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "vecequiv" => {
-                    vecequiv = get_attribute(&attributes, "vecequiv");
-                    self.expect_end_element("vecequiv");
+                    vecequiv = get_attribute(&attributes, "name");
+                    vecequiv = vecequiv.map(|t| trim_cmd_prefix(&t, self.ns).to_string());
+                    try!(self.expect_end_element("vecequiv"));
                 }
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "glx" => {
                     glx = Some(GlxOpcode {
-                        ty:      get_attribute(&attributes, "type").unwrap(),
-                        opcode:  get_attribute(&attributes, "opcode").unwrap(),
+                        ty:      try!(self.expect_attribute(&attributes, "type")),
+                        opcode:  try!(self.expect_attribute(&attributes, "opcode")),
                         name:    get_attribute(&attributes, "name"),
                         comment: get_attribute(&attributes, "comment"),
                     });
-                    self.expect_end_element("glx");
+                    try!(self.expect_end_element("glx"));
                 }
                 XmlEvent::EndElement{ref name} if name.local_name == "command" => break,
-                msg => panic!("Expected </command>, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
         let is_safe = params.len() <= 0 || params.iter().all(|p| !p.ty.contains('*'));
 
-        Cmd {
+        Ok(Cmd {
             proto: proto,
             params: params,
             is_safe: is_safe,
             alias: alias,
             vecequiv: vecequiv,
             glx: glx,
-        }
+        })
     }
 
-    fn consume_binding(&self, outside_tag: &str, group: Option<String>) -> Binding {
+    fn consume_binding(&self, outside_tag: &str, attributes: &[OwnedAttribute]) -> Result<Binding, Error> {
         // consume type
         let mut ty = String::new();
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 XmlEvent::Characters(ch) => ty.push_str(&ch),
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "ptype" => (),
                 XmlEvent::EndElement{ref name} if name.local_name == "ptype" => (),
                 XmlEvent::StartElement{ref name, ..} if name.local_name == "name" => break,
-                msg => panic!("Expected binding, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
 
         // consume identifier
-        let ident = self.expect_characters();
-        self.expect_end_element("name");
+        let ident = try!(self.expect_characters());
+        try!(self.expect_end_element("name"));
 
         // consume the type suffix
         loop {
-            match self.recv() {
+            match try!(self.recv()) {
                 XmlEvent::Characters(ch) => ty.push_str(&ch),
                 XmlEvent::EndElement{ref name} if name.local_name == outside_tag => break,
-                msg => panic!("Expected binding, found: {:?}", msg),
+                msg => return Err(self.unexpected(&msg)),
             }
         }
 
-        Binding {
+        Ok(Binding {
             ident: ident,
             ty: ty,
-            group: group,
+            group: get_attribute(attributes, "group"),
+            len: get_attribute(attributes, "len").map(|len| Len::parse(&len)),
+            class: get_attribute(attributes, "class"),
+            kind: get_attribute(attributes, "kind"),
+        })
+    }
+}
+
+/// Works out the kind of a type definition from the C code surrounding its name.
+fn parse_type_def(prefix: &str, suffix: &str) -> TypeDef {
+    let code = format!("{} NAME {}", prefix, suffix);
+
+    if prefix.starts_with("DECLARE_HANDLE(") {
+        TypeDef::Handle
+    } else if prefix.starts_with("typedef union") ||
+              code.lines().any(|line| line.trim_left().starts_with('#')) {
+        TypeDef::Unsupported(code)
+    } else if let (Some(start), Some(end)) = (code.find('{'), code.rfind('}')) {
+        // `struct NAME { ... };` or `typedef struct { ... } NAME;`
+        TypeDef::Struct(parse_decls(&code[start + 1 .. end], ';'))
+    } else if prefix.starts_with("typedef") && prefix.ends_with('*') && prefix.contains('(') {
+        // `typedef <ret> (*NAME)(<params>);`
+        let ret = prefix["typedef".len() .. prefix.find('(').unwrap()].trim().to_string();
+        let params = match (suffix.find('('), suffix.rfind(')')) {
+            (Some(start), Some(end)) if start < end => &suffix[start + 1 .. end],
+            _ => return TypeDef::Unsupported(code),
+        };
+        let params = if params.trim() == "void" { Vec::new() } else { parse_decls(params, ',') };
+        TypeDef::FnPtr { ret: ret, params: params }
+    } else if prefix.starts_with("typedef") && suffix == ";" {
+        TypeDef::Alias(prefix["typedef".len()..].trim().to_string())
+    } else {
+        TypeDef::Unsupported(code)
+    }
+}
+
+/// Parses a list of C declarations such as struct fields or function parameters.
+///
+/// Declarations like `int x, y` which share a type are split into one binding per name.
+fn parse_decls(code: &str, separator: char) -> Vec<Binding> {
+    // strip comments
+    let mut stripped = String::new();
+    let mut rest = code;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start..].find("*/") {
+            Some(end) => &rest[start + end + 2 ..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+
+    let mut decls = Vec::new();
+    for decl in stripped.split(separator).map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        let mut declarators = decl.split(',');
+        let first = split_decl(declarators.next().unwrap());
+        // the pointers and array dimensions belong to the declarator, so they are not shared
+        // by the other names
+        let base = first.ty.split(|c| c == '*' || c == '[').next().unwrap().trim().to_string();
+        decls.push(first);
+        for declarator in declarators {
+            decls.push(split_decl(&format!("{} {}", base, declarator.trim())));
         }
     }
+    decls
+}
+
+/// Parses a single C declarator like `const GLchar *message` or `char pipeName[80]`.
+fn split_decl(decl: &str) -> Binding {
+    // normalize whitespace and put spaces around the pointers
+    let decl = decl.replace("*", " * ").split_whitespace().collect::<Vec<_>>().join(" ");
+    let (decl, array) = match decl.find('[') {
+        Some(pos) => (decl[..pos].trim(), Some(&decl[pos..])),
+        None => (&decl[..], None),
+    };
+    let name_start = decl.rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                         .map(|pos| pos + 1).unwrap_or(0);
+    let ty = decl[..name_start].trim();
+
+    // a parameter without a name, like `GLenum`
+    let (ty, ident) = if ty.is_empty() || ty == "const" || ty == "struct" {
+        (decl, "")
+    } else {
+        (ty, &decl[name_start..])
+    };
+    let ty = match array {
+        Some(array) => format!("{} {}", ty, array.replace(" ", "")),
+        None => ty.to_string(),
+    };
+
+    Binding { ident: ident.to_string(), ty: ty, group: None, len: None, class: None, kind: None }
 }
 
 fn get_attribute(a: &[OwnedAttribute], name: &str) -> Option<String> {
     a.iter().find(|a| a.name.local_name == name).map(|e| e.value.clone())
 }
 
-trait FromXML {
-    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Self;
+trait FromXML: Sized {
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<Self, Error>;
 }
 
 impl FromXML for Require {
-    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Require {
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<Require, Error> {
         debug!("Doing a FromXML on Require");
+        let api = get_attribute(a, "api");
+        let profile = get_attribute(a, "profile");
         let comment = get_attribute(a, "comment");
-        let (enums, commands) = r.consume_two("enum", "command", "require");
-        Require {
+        let (enums, commands) = try!(r.consume_two("enum", "command", "require"));
+        Ok(Require {
+            api: api,
+            profile: profile,
             comment: comment,
             enums: enums,
             commands: commands
-        }
+        })
     }
 }
 
 impl FromXML for Remove {
-    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Remove {
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<Remove, Error> {
         debug!("Doing a FromXML on Remove");
-        let profile = get_attribute(a, "profile").unwrap();
-        let comment = get_attribute(a, "comment").unwrap();
-        let (enums, commands) = r.consume_two("enum", "command", "remove");
+        let api = get_attribute(a, "api");
+        let profile = get_attribute(a, "profile");
+        let comment = get_attribute(a, "comment");
+        let (enums, commands) = try!(r.consume_two("enum", "command", "remove"));
 
-        Remove {
+        Ok(Remove {
+            api: api,
             profile: profile,
             comment: comment,
             enums: enums,
             commands: commands
-        }
+        })
     }
 }
 
 impl FromXML for Feature {
-    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Feature {
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<Feature, Error> {
         debug!("Doing a FromXML on Feature");
-        let api      = get_attribute(a, "api").unwrap();
-        let name     = get_attribute(a, "name").unwrap();
-        let number   = get_attribute(a, "number").unwrap();
+        let api      = try!(r.expect_attribute(a, "api"));
+        let name     = try!(r.expect_attribute(a, "name"));
+        let number   = try!(try!(r.expect_attribute(a, "number")).parse());
 
         debug!("Found api = {}, name = {}, number = {}", api, name, number);
 
-        let (require, remove) = r.consume_two("require", "remove", "feature");
+        let (require, remove) = try!(r.consume_two("require", "remove", "feature"));
 
-        Feature {
+        Ok(Feature {
             api: api,
             name: name,
             number: number,
             requires: require,
             removes: remove
-        }
+        })
     }
 }
 
 impl FromXML for Extension {
-    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Extension {
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<Extension, Error> {
         debug!("Doing a FromXML on Extension");
-        let name = get_attribute(a, "name").unwrap();
-        let supported = get_attribute(a, "supported").unwrap().split('|').map(|x| x.to_string()).collect::<Vec<String>>();
+        let name = try!(r.expect_attribute(a, "name"));
+        let supported = try!(r.expect_attribute(a, "supported")).split('|').map(|x| x.to_string()).collect::<Vec<String>>();
         let mut require = Vec::new();
         loop {
-            match r.recv() {
+            match try!(r.recv()) {
                 XmlEvent::StartElement{ref name, ref attributes, ..} if name.local_name == "require" => {
-                    require.push(FromXML::convert(r, &attributes));
+                    require.push(try!(FromXML::convert(r, &attributes)));
                 }
                 XmlEvent::EndElement{ref name} if name.local_name == "extension" => break,
-                msg => panic!("Unexpected message {:?}", msg)
+                msg => return Err(r.unexpected(&msg)),
             }
         }
 
-        Extension {
+        Ok(Extension {
             name: name,
             supported: supported,
             requires: require
-        }
+        })
     }
 }
 
 impl FromXML for String {
-    fn convert<R: io::Read>(_: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> String {
-        get_attribute(a, "name").unwrap()
+    fn convert<R: io::Read>(r: &RegistryBuilder<R>, a: &[OwnedAttribute]) -> Result<String, Error> {
+        r.expect_attribute(a, "name")
     }
 }
@@ -0,0 +1,78 @@
+// Copyright 2015 The gl-rs developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use Error;
+
+/// The environment variable naming a directory of registry files that replace the bundled ones.
+///  See `Source::bundled`.
+pub const XML_DIR_VAR: &'static str = "GL_GENERATOR_XML_DIR";
+
+/// The registry XML to generate bindings from.
+pub enum Source<'a> {
+    /// The content of a registry file, like `khronos_api::GL_XML`.
+    Bytes(&'a [u8]),
+    /// The path of a registry file, like a newer `gl.xml` or the XML of a vendor.
+    Path(PathBuf),
+}
+
+impl<'a> Source<'a> {
+    /// Returns the bundled registry `bytes`, unless the `GL_GENERATOR_XML_DIR` environment
+    ///  variable is set, in which case the file `name` (like `"gl.xml"`) of that directory is
+    ///  used instead.
+    pub fn bundled(name: &str, bytes: &'a [u8]) -> Source<'a> {
+        match env::var_os(XML_DIR_VAR) {
+            Some(dir) => Source::Path(Path::new(&dir).join(name)),
+            None => Source::Bytes(bytes),
+        }
+    }
+
+    /// Returns the content of the registry.
+    pub fn read(&self) -> Result<Cow<'a, [u8]>, Error> {
+        match *self {
+            Source::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+            Source::Path(ref path) => {
+                let mut data = Vec::new();
+                match File::open(path).and_then(|mut file| file.read_to_end(&mut data)) {
+                    Ok(_) => Ok(Cow::Owned(data)),
+                    Err(err) => Err(Error::ReadRegistry { path: path.clone(), err: err }),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a [u8]> for Source<'a> {
+    fn from(bytes: &'a [u8]) -> Source<'a> {
+        Source::Bytes(bytes)
+    }
+}
+
+impl<'a> From<&'a Path> for Source<'a> {
+    fn from(path: &'a Path) -> Source<'a> {
+        Source::Path(path.to_path_buf())
+    }
+}
+
+impl<'a> From<PathBuf> for Source<'a> {
+    fn from(path: PathBuf) -> Source<'a> {
+        Source::Path(path)
+    }
+}
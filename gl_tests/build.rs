@@ -7,6 +7,8 @@ use std::fs::File;
 use std::path::*;
 use std::io::BufWriter;
 
+use gl_generator::generators::{Options, WithOptions};
+
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
 
@@ -14,7 +16,7 @@ fn main() {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
 
 
@@ -24,6 +26,7 @@ fn main() {
     //if os::getenv("PROFILE").unwrap() == "test" {
         write_test_gen_symbols(&Path::new(&dest));
         write_test_no_warnings(&Path::new(&dest));
+        write_test_mock(&Path::new(&dest));
     //}
 }
 
@@ -34,7 +37,7 @@ fn write_test_gen_symbols(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -42,7 +45,7 @@ fn write_test_gen_symbols(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -50,7 +53,7 @@ fn write_test_gen_symbols(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -58,7 +61,7 @@ fn write_test_gen_symbols(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -66,7 +69,7 @@ fn write_test_gen_symbols(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 }
@@ -78,7 +81,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -86,7 +89,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -94,7 +97,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -102,7 +105,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -110,7 +113,119 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Gl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "4.5", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_global {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_static {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StaticGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StructGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_static_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StaticStructGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_debug_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::DebugStructGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_vec_helpers_global {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, vec_helpers()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_vec_helpers_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StructGenerator, vec_helpers()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_deprecations_vec_helpers_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StructGenerator, deprecations_vec_helpers()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_enum_groups_global {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, enum_groups()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_enum_groups_debug_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::DebugStructGenerator, enum_groups()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_enum_groups_vec_helpers_struct {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StructGenerator, enum_groups_vec_helpers()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_mock {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::MockGenerator, deprecations()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_safe_wrappers {{")).unwrap();
+    gl_generator::generate_bindings(gl_generator::SafeWrapperGenerator,
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "compatibility",
+                                    &mut file).unwrap();
+    (writeln!(&mut file, "}}")).unwrap();
+
+    (writeln!(&mut file, "mod gl_safe_wrappers_enum_groups {{")).unwrap();
+    gl_generator::generate_bindings(WithOptions(gl_generator::SafeWrapperGenerator, enum_groups()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -120,7 +235,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -128,7 +243,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -136,7 +251,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -144,7 +259,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -152,7 +267,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Glx,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GLX_XML, vec![], "1.4", "core",
+                                    khronos_api::GLX_XML, vec![], Some(gl_generator::Version::new(1, 4)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -162,7 +277,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -170,7 +285,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -178,7 +293,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -186,7 +301,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -194,7 +309,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Wgl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::WGL_XML, vec![], "1.0", "core",
+                                    khronos_api::WGL_XML, vec![], Some(gl_generator::Version::new(1, 0)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -204,7 +319,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gles1,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "1.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(1, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -212,7 +327,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Gles1,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "1.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(1, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -220,7 +335,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Gles1,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "1.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(1, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -228,7 +343,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Gles1,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "1.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(1, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -236,7 +351,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Gles1,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "1.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(1, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -246,7 +361,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -254,7 +369,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -262,7 +377,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -270,7 +385,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -278,7 +393,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Gles2,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::GL_XML, vec![], "3.1", "core",
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(3, 1)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -288,7 +403,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::GlobalGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -296,7 +411,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -304,7 +419,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StructGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -312,7 +427,7 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::StaticStructGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
@@ -320,22 +435,49 @@ fn write_test_no_warnings(dest: &Path) {
     gl_generator::generate_bindings(gl_generator::DebugStructGenerator,
                                     gl_generator::registry::Ns::Egl,
                                     gl_generator::Fallbacks::All,
-                                    khronos_api::EGL_XML, vec![], "1.5", "core",
+                                    khronos_api::EGL_XML, vec![], Some(gl_generator::Version::new(1, 5)), "core",
                                     &mut file).unwrap();
     (writeln!(&mut file, "}}")).unwrap();
 
 
 }
 
+fn write_test_mock(dest: &Path) {
+    let mut file = BufWriter::new(File::create(&dest.join("test_mock.rs")).unwrap());
+
+    gl_generator::generate_bindings(WithOptions(gl_generator::MockGenerator, enum_groups()),
+                                    gl_generator::registry::Ns::Gl,
+                                    gl_generator::Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(gl_generator::Version::new(4, 5)), "core",
+                                    &mut file).unwrap();
+}
+
+fn deprecations() -> Options {
+    Options { deprecations: true, .. Options::default() }
+}
+
+fn vec_helpers() -> Options {
+    Options { vec_helpers: true, .. Options::default() }
+}
+
+fn deprecations_vec_helpers() -> Options {
+    Options { deprecations: true, vec_helpers: true, .. Options::default() }
+}
+
+fn enum_groups() -> Options {
+    Options { enum_groups: true, .. Options::default() }
+}
+
+fn enum_groups_vec_helpers() -> Options {
+    Options { enum_groups: true, vec_helpers: true, .. Options::default() }
+}
+
 fn build_egl_symbols() -> &'static str {
     "
         #![allow(non_camel_case_types)]
 
         use libc;
 
-        pub type khronos_utime_nanoseconds_t = libc::c_int;
-        pub type khronos_uint64_t = libc::uint64_t;
-        pub type khronos_ssize_t = libc::ssize_t;
         pub type EGLNativeDisplayType = *const libc::c_void;
         pub type EGLNativePixmapType = *const libc::c_void;
         pub type EGLNativeWindowType = *const libc::c_void;
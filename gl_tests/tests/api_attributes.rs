@@ -0,0 +1,58 @@
+//! Checks that the `api` and `profile` attributes of `<require>` and `<remove>` blocks restrict
+//!  them to an API or a profile.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Profile, Version};
+use gl_generator::registry::{Ns, Registry};
+
+use common::{cmds, enums};
+
+const XML: &'static [u8] = include_bytes!("fixtures/api_attributes.xml");
+
+fn filter(ns: Ns, version: Version, profile: Option<Profile>) -> Registry {
+    let extensions = vec!["GL_KHR_debug".to_string()].into();
+    common::filter(XML, ns, Some(version), profile, extensions).unwrap()
+}
+
+#[test]
+fn extension_requires_of_gl() {
+    let registry = filter(Ns::Gl, Version::new(1, 0), Some(Profile::Core));
+    assert_eq!(cmds(&registry), ["Clear", "DebugMessageInsert"]);
+    assert_eq!(enums(&registry), ["BUFFER", "DEBUG_OUTPUT"]);
+}
+
+#[test]
+fn extension_requires_of_gles2() {
+    let registry = filter(Ns::Gles2, Version::new(2, 0), None);
+    assert_eq!(cmds(&registry), ["Clear", "DebugMessageInsertKHR"]);
+    assert_eq!(enums(&registry), ["BUFFER_KHR", "DEBUG_OUTPUT"]);
+}
+
+#[test]
+fn require_of_a_profile() {
+    let core = filter(Ns::Gl, Version::new(1, 0), Some(Profile::Core));
+    assert!(!cmds(&core).contains(&"Begin"));
+    assert!(!enums(&core).contains(&"QUADS"));
+
+    let compatibility = filter(Ns::Gl, Version::new(1, 0), Some(Profile::Compatibility));
+    assert_eq!(cmds(&compatibility), ["Begin", "Clear", "DebugMessageInsert"]);
+    assert_eq!(enums(&compatibility), ["BUFFER", "DEBUG_OUTPUT", "QUADS"]);
+}
+
+#[test]
+fn remove_of_another_api() {
+    let registry = filter(Ns::Gl, Version::new(3, 0), Some(Profile::Core));
+    assert!(enums(&registry).contains(&"STACK_UNDERFLOW"));
+}
+
+#[test]
+fn remove_of_a_profile() {
+    let core = filter(Ns::Gl, Version::new(3, 0), Some(Profile::Core));
+    assert!(cmds(&core).contains(&"Clear"));
+
+    let compatibility = filter(Ns::Gl, Version::new(3, 0), Some(Profile::Compatibility));
+    assert!(!cmds(&compatibility).contains(&"Clear"));
+}
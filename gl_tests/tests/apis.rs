@@ -0,0 +1,61 @@
+//! Checks the APIs that are described by an `ApiInfo`, like `glcore` and `glsc2`.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Bindings, Error, ExtensionSelector, Profile, Version};
+use gl_generator::registry::{Ns, Registry};
+
+use common::cmds;
+
+const XML: &'static [u8] = include_bytes!("fixtures/apis.xml");
+
+fn filter(ns: Ns, version: Version, profile: Profile) -> Result<Registry, Error> {
+    let all = ExtensionSelector { all_supported: true, .. ExtensionSelector::default() };
+    Bindings::new(ns, version, profile).source(XML).extensions(all).registry()
+}
+
+fn exts(registry: &Registry) -> Vec<&str> {
+    registry.extensions.iter().map(|e| &e.name[..]).collect()
+}
+
+#[test]
+fn info() {
+    assert_eq!(Ns::Glcore.to_string(), "glcore");
+    assert_eq!(Ns::Glcore.info().api, "gl");
+    assert_eq!(Ns::Glcore.fmt_struct_name(), "Gl");
+    assert_eq!(Ns::Glsc2.to_string(), "glsc2");
+    assert_eq!(Ns::Glsc2.fmt_struct_name(), "Glsc2");
+    assert_eq!("glcore".parse::<Ns>().ok().map(|ns| ns.info()), Some(Ns::Glcore.info()));
+    assert_eq!("glsc2".parse::<Ns>().ok().map(|ns| ns.info()), Some(Ns::Glsc2.info()));
+}
+
+#[test]
+fn glcore_is_the_core_profile() {
+    // the requested profile is replaced by the core profile
+    for &profile in [Profile::Core, Profile::Compatibility].iter() {
+        let registry = filter(Ns::Glcore, Version::new(1, 0), profile).unwrap();
+        assert_eq!(cmds(&registry), ["Clear", "FenceSync"]);
+        assert_eq!(exts(&registry), ["GL_ARB_sync"]);
+    }
+}
+
+#[test]
+fn gl_compatibility() {
+    let registry = filter(Ns::Gl, Version::new(1, 0), Profile::Compatibility).unwrap();
+    assert_eq!(cmds(&registry), ["Begin", "Clear", "FenceSync", "FinishFenceNV"]);
+    assert_eq!(exts(&registry), ["GL_ARB_sync", "GL_NV_fence"]);
+}
+
+#[test]
+fn glsc2() {
+    let registry = filter(Ns::Glsc2, Version::new(2, 0), Profile::Core).unwrap();
+    assert_eq!(cmds(&registry), ["Clear"]);
+    assert!(exts(&registry).is_empty());
+
+    match filter(Ns::Glsc2, Version::new(1, 0), Profile::Core) {
+        Err(Error::UnknownVersion { ref api, .. }) => assert_eq!(api, "glsc2"),
+        _ => panic!("glsc2 1.0 is not in the registry"),
+    }
+}
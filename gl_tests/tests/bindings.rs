@@ -0,0 +1,64 @@
+//! Checks that `Bindings` generates the same bindings as `generate_bindings`, and validates its
+//!  configuration before writing anything.
+
+extern crate gl_generator;
+
+use gl_generator::{Bindings, Error, Fallbacks, Profile, Version};
+use gl_generator::generators::Options;
+use gl_generator::registry::{ApiInfo, Ns};
+
+const XML: &'static [u8] = include_bytes!("fixtures/profiles.xml");
+
+static CUSTOM: ApiInfo = ApiInfo {
+    name: "gl", api: "gl", profile: None, cmd_prefix: "gl", enum_prefix: "GL_", struct_name: "Gl",
+};
+
+#[test]
+fn same_as_generate_bindings() {
+    let mut expected = Vec::new();
+    gl_generator::generate_bindings(gl_generator::StructGenerator, Ns::Gl, Fallbacks::None, XML,
+                                    vec![], Some(Version::new(3, 2)), "core", &mut expected).unwrap();
+
+    let mut dest = Vec::new();
+    Bindings::new(Ns::Gl, Version::new(3, 2), Profile::Core)
+        .source(XML)
+        .fallbacks(Fallbacks::None)
+        .generator(gl_generator::StructGenerator)
+        .write_to(&mut dest)
+        .unwrap();
+    assert_eq!(String::from_utf8(dest).unwrap(), String::from_utf8(expected).unwrap());
+}
+
+#[test]
+fn options() {
+    let mut dest = Vec::new();
+    Bindings::new(Ns::Gl, Version::new(1, 0), Profile::Core)
+        .source(XML)
+        .options(Options { deprecations: true, .. Options::default() })
+        .write_to(&mut dest)
+        .unwrap();
+    let dest = String::from_utf8(dest).unwrap();
+    assert!(dest.contains("#[deprecated(note = \"removed from core profile in GL 3.2\")]"));
+}
+
+#[test]
+fn validates_before_writing() {
+    let bindings = Bindings::new(Ns::Gl, Version::new(9, 9), Profile::Core).source(XML);
+    match bindings.registry() {
+        Err(Error::UnknownVersion { .. }) => (),
+        _ => panic!("expected an unknown version"),
+    }
+
+    let mut dest = Vec::new();
+    assert!(bindings.write_to(&mut dest).is_err());
+    assert!(dest.is_empty());
+}
+
+#[test]
+fn custom_api_needs_a_source() {
+    match Bindings::new(Ns::Custom(&CUSTOM), None, Profile::Core).registry() {
+        Err(Error::MissingSource(ref api)) if api == "gl" => (),
+        _ => panic!("expected a missing source"),
+    }
+    assert!(Bindings::new(Ns::Custom(&CUSTOM), None, Profile::Core).source(XML).registry().is_ok());
+}
@@ -0,0 +1,35 @@
+//! Helpers shared by the tests of the registry, which only differ in their fixtures and filters.
+
+// each test only uses some of them
+#![allow(dead_code)]
+
+use gl_generator::{Error, ExtensionSelector, Fallbacks, Profile, Version};
+use gl_generator::registry::{Filter, Ns, Registry};
+
+/// Parses a fixture, keeping the definitions selected by the parameters of the filter.
+pub fn filter(xml: &[u8], ns: Ns, version: Option<Version>, profile: Option<Profile>,
+              extensions: ExtensionSelector) -> Result<Registry, Error>
+{
+    let filter = Filter {
+        fallbacks: Fallbacks::All,
+        extensions: extensions,
+        profile: profile,
+        version: version,
+        api: ns.to_string(),
+    };
+    Registry::from_xml(xml, ns, Some(filter))
+}
+
+/// Returns the names of the commands of a registry, sorted.
+pub fn cmds(registry: &Registry) -> Vec<&str> {
+    let mut cmds: Vec<&str> = registry.cmds.iter().map(|c| &c.proto.ident[..]).collect();
+    cmds.sort();
+    cmds
+}
+
+/// Returns the names of the enums of a registry, sorted.
+pub fn enums(registry: &Registry) -> Vec<&str> {
+    let mut enums: Vec<&str> = registry.enums.iter().map(|e| &e.ident[..]).collect();
+    enums.sort();
+    enums
+}
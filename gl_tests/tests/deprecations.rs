@@ -0,0 +1,76 @@
+//! Checks the `#[deprecated]` attributes written for what a later version removes from the core
+//!  profile.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Fallbacks, Profile, Version};
+use gl_generator::generators::{Options, WithOptions};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/profiles.xml");
+const NOTE: &'static str = "#[deprecated(note = \"removed from core profile in GL 3.2\")]";
+
+fn filter(version: Version, profile: Profile) -> Registry {
+    common::filter(XML, Ns::Gl, Some(version), Some(profile), Default::default()).unwrap()
+}
+
+fn generate(options: Options, version: Version, profile: &str) -> String {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(WithOptions(gl_generator::GlobalGenerator, options), Ns::Gl,
+                                    Fallbacks::All, XML, vec![], Some(version), profile,
+                                    &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn earlier_core_versions() {
+    let registry = filter(Version::new(1, 0), Profile::Core);
+    assert_eq!(registry.removed_from_core("Begin"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("Rotatef"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("QUADS"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("Clear"), None);
+}
+
+#[test]
+fn compatibility_profile() {
+    let registry = filter(Version::new(3, 3), Profile::Compatibility);
+    assert_eq!(registry.removed_from_core("Begin"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("STACK_OVERFLOW"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("Clear"), None);
+}
+
+#[test]
+fn compatibility_profile_after_restore() {
+    // 4.3 core brings `STACK_OVERFLOW` back
+    let registry = filter(Version::new(4, 3), Profile::Compatibility);
+    assert_eq!(registry.removed_from_core("Begin"), Some(Version::new(3, 2)));
+    assert_eq!(registry.removed_from_core("STACK_OVERFLOW"), None);
+    assert_eq!(registry.removed_from_core("PushDebugGroup"), None);
+}
+
+#[test]
+fn applied_removals() {
+    // 4.3 core brings `STACK_OVERFLOW` back, which is then not deprecated
+    let registry = filter(Version::new(4, 3), Profile::Core);
+    assert_eq!(registry.removed_from_core("STACK_OVERFLOW"), None);
+    assert_eq!(registry.removed_from_core("Begin"), None);
+}
+
+#[test]
+fn attributes() {
+    let options = Options { deprecations: true, .. Options::default() };
+    let bindings = generate(options, Version::new(1, 0), "core");
+    // `Begin`, `End`, `Rotatef`, `QUADS` and `STACK_OVERFLOW`
+    assert_eq!(bindings.matches(NOTE).count(), 5);
+    let begin = bindings.find("pub unsafe fn Begin(").unwrap();
+    let note = bindings[.. begin].rfind(NOTE).unwrap();
+    assert!(!bindings[note .. begin].contains("fn "));
+}
+
+#[test]
+fn disabled_by_default() {
+    let bindings = generate(Options::default(), Version::new(1, 0), "core");
+    assert!(!bindings.contains("#[deprecated"));
+}
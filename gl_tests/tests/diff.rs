@@ -0,0 +1,95 @@
+//! Checks the comparison of two registries and its reports.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Profile, Version};
+use gl_generator::registry::{diff, Change, ChangeKind, Diff, Ns, Reason, Registry};
+
+const OLD: &'static [u8] = include_bytes!("fixtures/diff_old.xml");
+const NEW: &'static [u8] = include_bytes!("fixtures/diff_new.xml");
+
+fn filter(xml: &[u8], version: Version) -> Registry {
+    common::filter(xml, Ns::Gl, Some(version), Some(Profile::Core), Default::default()).unwrap()
+}
+
+fn compare() -> Diff {
+    diff(&filter(OLD, Version::new(1, 0)), &filter(NEW, Version::new(3, 2)), Ns::Gl)
+}
+
+fn summary(changes: &[Change]) -> Vec<(&str, ChangeKind)> {
+    changes.iter().map(|c| (&c.ident[..], c.kind)).collect()
+}
+
+fn change<'a>(changes: &'a [Change], ident: &str) -> &'a Change {
+    changes.iter().find(|c| c.ident == ident).unwrap()
+}
+
+#[test]
+fn identical_registries() {
+    let registry = filter(NEW, Version::new(3, 2));
+    assert!(diff(&registry, &registry, Ns::Gl).is_empty());
+}
+
+#[test]
+fn commands() {
+    let diff = compare();
+    assert_eq!(summary(&diff.cmds), [("Begin", ChangeKind::Removed),
+                                     ("Clear", ChangeKind::Changed),
+                                     ("FenceSync", ChangeKind::Added),
+                                     ("GetError", ChangeKind::Changed),
+                                     ("Obsolete", ChangeKind::Removed)]);
+
+    assert_eq!(change(&diff.cmds, "FenceSync").reasons,
+               [Reason::IntroducedIn(vec!["GL_VERSION_3_2".to_string()])]);
+    assert_eq!(change(&diff.cmds, "Begin").reasons, [Reason::RemovedIn {
+        feature: "GL_VERSION_3_2".to_string(),
+        version: Some(Version::new(3, 2)),
+        profile: Some("core".to_string()),
+    }]);
+    assert_eq!(change(&diff.cmds, "Obsolete").reasons, [Reason::NotRequired]);
+    assert_eq!(change(&diff.cmds, "Clear").reasons, [Reason::Params {
+        old: "".to_string(),
+        new: "GLbitfield mask".to_string(),
+    }]);
+    assert_eq!(change(&diff.cmds, "GetError").reasons,
+               [Reason::ReturnType { old: "GLenum".to_string(), new: "GLuint".to_string() }]);
+}
+
+#[test]
+fn enums() {
+    let diff = compare();
+    assert_eq!(summary(&diff.enums), [("OBSOLETE", ChangeKind::Removed),
+                                      ("ONE", ChangeKind::Changed),
+                                      ("QUADS", ChangeKind::Removed),
+                                      ("SYNC_GPU_COMMANDS_COMPLETE", ChangeKind::Added)]);
+
+    assert_eq!(change(&diff.enums, "ONE").reasons,
+               [Reason::Value { old: "1".to_string(), new: "0x1".to_string() }]);
+    assert_eq!(change(&diff.enums, "QUADS").fmt_reasons(), "removed in 3.2 core");
+}
+
+#[test]
+fn text() {
+    let mut dest = Vec::new();
+    compare().write_text(&mut dest).unwrap();
+    let text = String::from_utf8(dest).unwrap();
+    assert!(text.starts_with("Commands: 1 added, 2 removed, 2 changed\n"));
+    assert!(text.contains("\n  - Begin (removed in 3.2 core)\n"));
+    assert!(text.contains("\n  + FenceSync (introduced in GL_VERSION_3_2)\n"));
+    assert!(text.contains("\nEnums: 1 added, 2 removed, 1 changed\n"));
+    assert!(text.contains("\n  ~ ONE (value changed from `1` to `0x1`)\n"));
+}
+
+#[test]
+fn markdown() {
+    let mut dest = Vec::new();
+    compare().write_markdown(&mut dest).unwrap();
+    let markdown = String::from_utf8(dest).unwrap();
+    assert!(markdown.starts_with("## Added commands\n\n- `FenceSync`: introduced in GL_VERSION_3_2\n\n"));
+    assert!(markdown.contains("## Removed commands\n\n- `Begin`: removed in 3.2 core\n"));
+    assert!(markdown.contains("- `Obsolete`: not required by the new registry\n"));
+    assert!(markdown.contains("## Changed enums\n\n- `ONE`: value changed from `1` to `0x1`\n"));
+    assert!(markdown.contains("## Added enums\n\n- `SYNC_GPU_COMMANDS_COMPLETE`"));
+}
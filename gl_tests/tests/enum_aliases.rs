@@ -0,0 +1,88 @@
+//! Checks the resolution of the `alias` attributes of enums.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, Fallbacks, Version};
+use gl_generator::generators::{Options, WithOptions};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/aliases.xml");
+
+fn filtered() -> Registry {
+    common::filter(XML, Ns::Gles2, Some(Version::new(2, 0)), None, Default::default()).unwrap()
+}
+
+fn generate(options: Options) -> String {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(WithOptions(gl_generator::StructGenerator, options), Ns::Gles2,
+                                    Fallbacks::All, XML, vec![], Some(Version::new(2, 0)), "core",
+                                    &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn resolve_chains() {
+    let registry = Registry::from_xml(XML, Ns::Gles2, None).unwrap();
+    assert_eq!(registry.resolve_enum("CLIP_DISTANCE0_EXT").unwrap().unwrap().ident, "CLIP_PLANE0");
+    assert_eq!(registry.resolve_enum("CLIP_PLANE0").unwrap().unwrap().ident, "CLIP_PLANE0");
+    assert!(registry.resolve_enum("CLIP_DISTANCE1").unwrap().is_none());
+}
+
+#[test]
+fn conflicting_values() {
+    let registry = Registry::from_xml(XML, Ns::Gles2, None).unwrap();
+    match registry.resolve_enum("FOO") {
+        Err(Error::ConflictingEnumAlias { ref name, ref alias, ref values }) => {
+            assert_eq!(name, "FOO");
+            assert_eq!(alias, "BAR");
+            assert_eq!(values, &["0x0001", "0x0002"]);
+        }
+        _ => panic!("`FOO` and `BAR` have different values"),
+    }
+    assert!(registry.check_enum_aliases().is_err());
+}
+
+#[test]
+fn cycles() {
+    let registry = Registry::from_xml(XML, Ns::Gles2, None).unwrap();
+    match registry.resolve_enum("CYCLE_A") {
+        Err(Error::EnumAliasCycle(ref name)) => assert_eq!(name, "CYCLE_A"),
+        _ => panic!("the aliases of `CYCLE_A` loop"),
+    }
+}
+
+#[test]
+fn equivalent_enums() {
+    let registry = filtered();
+    let mut equivalents: Vec<(&str, &str)> = registry.equivalent_enums.iter()
+        .map(|e| (&e.enm.ident[..], &e.equivalent_to[..]))
+        .collect();
+    equivalents.sort();
+    // `BAR` and `CYCLE_B` are left out, since their aliases are broken
+    assert_eq!(equivalents, [("CLIP_DISTANCE0_EXT", "CLIP_DISTANCE0"), ("CLIP_PLANE0", "CLIP_DISTANCE0")]);
+}
+
+#[test]
+fn json_keeps_equivalent_enums() {
+    let registry = filtered();
+    let mut json = Vec::new();
+    registry.write_json(&mut json).unwrap();
+    let reloaded = Registry::from_json(&json[..]).unwrap();
+    assert_eq!(reloaded.equivalent_enums.len(), 2);
+    assert_eq!(reloaded.equivalent_enums[0].equivalent_to, "CLIP_DISTANCE0");
+}
+
+#[test]
+fn generated_aliases() {
+    let bindings = generate(Options { enum_aliases: true, .. Options::default() });
+    let doc = bindings.find("/// Equivalent to `CLIP_DISTANCE0`.").unwrap();
+    assert!(bindings[doc ..].contains("pub const CLIP_PLANE0: types::GLenum = 0x3000;"));
+    assert!(bindings.contains("pub const CLIP_DISTANCE0_EXT: types::GLenum = 0x3000;"));
+    assert!(!bindings.contains("pub const BAR:"));
+
+    let bindings = generate(Options::default());
+    assert!(bindings.contains("pub const CLIP_DISTANCE0: types::GLenum = 0x3000;"));
+    assert!(!bindings.contains("CLIP_PLANE0"));
+}
@@ -0,0 +1,51 @@
+//! Checks the choice between the definitions of an enum that the registry defines more than once.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, ExtensionSelector, Profile, Version};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/enum_apis.xml");
+
+fn filter(ns: Ns, version: Version, extensions: ExtensionSelector) -> Result<Registry, Error> {
+    common::filter(XML, ns, Some(version), Some(Profile::Core), extensions)
+}
+
+fn value<'a>(registry: &'a Registry, ident: &str) -> Vec<&'a str> {
+    registry.enums.iter().filter(|e| e.ident == ident).map(|e| &e.value[..]).collect()
+}
+
+#[test]
+fn generic_definition() {
+    let registry = filter(Ns::Gl, Version::new(1, 0), Default::default()).unwrap();
+    assert_eq!(value(&registry, "ACTIVE_PROGRAM_EXT"), ["0x8B8D"]);
+    assert_eq!(value(&registry, "COLOR_BUFFER_BIT"), ["0x4000"]);
+}
+
+#[test]
+fn api_specific_definition() {
+    let registry = filter(Ns::Gles2, Version::new(2, 0), Default::default()).unwrap();
+    assert_eq!(value(&registry, "ACTIVE_PROGRAM_EXT"), ["0x8259"]);
+    assert_eq!(value(&registry, "COLOR_BUFFER_BIT"), ["0x4000"]);
+}
+
+#[test]
+fn api_specific_definition_resolves_conflicts() {
+    let extensions = vec!["GL_EXT_ambiguous".to_string()].into();
+    let registry = filter(Ns::Gles2, Version::new(2, 0), extensions).unwrap();
+    assert_eq!(value(&registry, "AMBIGUOUS"), ["0x0003"]);
+}
+
+#[test]
+fn conflicting_definitions() {
+    let extensions = vec!["GL_EXT_ambiguous".to_string()].into();
+    match filter(Ns::Gl, Version::new(1, 0), extensions) {
+        Err(Error::ConflictingEnum { ref name, ref values }) => {
+            assert_eq!(name, "AMBIGUOUS");
+            assert_eq!(values, &["0x0001", "0x0002"]);
+        },
+        _ => panic!("the two definitions of `AMBIGUOUS` disagree"),
+    }
+}
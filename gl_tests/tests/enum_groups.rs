@@ -0,0 +1,98 @@
+//! Checks the newtypes generated for enum groups and bitmask groups, and the parameters that
+//!  take them.
+
+extern crate gl_generator;
+
+use gl_generator::{Fallbacks, Version};
+use gl_generator::generators::{Generator, Options, WithOptions};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/enum_groups.xml");
+
+fn generate<G>(generator: G, enum_groups: bool, version: Version) -> String where G: Generator {
+    let mut dest = Vec::new();
+    let options = Options { enum_groups: enum_groups, .. Options::default() };
+    gl_generator::generate_bindings(WithOptions(generator, options), Ns::Gl, Fallbacks::All, XML,
+                                    vec![], Some(version), "core", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn group_enums() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    let idents: Vec<&str> = registry.group_enums("PrimitiveType").unwrap().iter()
+                                    .map(|e| &e.ident[..]).collect();
+    assert_eq!(idents, ["POINTS", "TRIANGLES", "QUADS"]);
+    assert!(registry.group_enums("EnableCap").is_none());
+}
+
+#[test]
+fn newtypes() {
+    let global = generate(gl_generator::GlobalGenerator, true, Version::new(1, 0));
+    assert!(global.contains("#[repr(transparent)]"));
+    assert!(global.contains("pub struct PrimitiveType(pub GLenum);"));
+    assert!(global.contains("pub const TRIANGLES: PrimitiveType = PrimitiveType(0x0004);"));
+    // not named by any parameter
+    assert!(!global.contains("TextureTarget"));
+    assert!(!global.contains("UnusedGroup"));
+    // the plain constants stay
+    assert!(global.contains("pub const TEXTURE_2D: types::GLenum = 0x0DE1;"));
+}
+
+#[test]
+fn bitmask_groups() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    assert!(registry.is_bitmask_group("ClearBufferMask"));
+    assert!(registry.is_bitmask_group("AttribMask"));
+    assert!(registry.is_bitmask_group("BufferBits"));
+    assert!(!registry.is_bitmask_group("PrimitiveType"));
+}
+
+#[test]
+fn flags() {
+    let global = generate(gl_generator::GlobalGenerator, true, Version::new(1, 0));
+    assert!(global.contains("#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]"));
+    assert!(global.contains("pub struct ClearBufferMask(pub GLbitfield);"));
+    assert!(global.contains("pub const COLOR_BUFFER_BIT: ClearBufferMask = ClearBufferMask(0x00004000);"));
+    assert!(global.contains("pub fn contains(&self, other: ClearBufferMask) -> bool {"));
+    assert!(global.contains("impl ::std::ops::BitOr for ClearBufferMask {"));
+    assert!(global.contains("impl ::std::ops::BitAnd for ClearBufferMask {"));
+    assert!(global.contains("impl ::std::fmt::Debug for ClearBufferMask {"));
+    assert!(global.contains(r#"("DEPTH_BUFFER_BIT", ClearBufferMask::DEPTH_BUFFER_BIT)"#));
+    assert!(global.contains("pub unsafe fn Clear(mask: types::ClearBufferMask)"));
+    // the enums of the group don't get a derived `Debug`
+    assert!(global.contains("pub struct PrimitiveType(pub GLenum);"));
+}
+
+#[test]
+fn bitmask_constants() {
+    let global = generate(gl_generator::GlobalGenerator, false, Version::new(1, 0));
+    assert!(global.contains("pub const COLOR_BUFFER_BIT: types::GLbitfield = 0x00004000;"));
+    assert!(global.contains("pub const TRIANGLES: types::GLenum = 0x0004;"));
+}
+
+#[test]
+fn removed_enums() {
+    let global = generate(gl_generator::GlobalGenerator, true, Version::new(3, 2));
+    assert!(global.contains("pub const POINTS: PrimitiveType"));
+    assert!(!global.contains("pub const QUADS: PrimitiveType"));
+}
+
+#[test]
+fn parameters() {
+    let global = generate(gl_generator::GlobalGenerator, true, Version::new(1, 0));
+    assert!(global.contains("pub unsafe fn DrawArrays(mode: types::PrimitiveType, first: types::GLint"));
+    // groups that the registry doesn't define, and groups of other types
+    assert!(global.contains("pub unsafe fn Enable(cap: types::GLenum)"));
+    assert!(global.contains("pub unsafe fn Color3f(red: types::GLfloat"));
+
+    let struct_ = generate(gl_generator::StructGenerator, true, Version::new(1, 0));
+    assert!(struct_.contains("pub unsafe fn DrawArrays(&self, mode: types::PrimitiveType"));
+}
+
+#[test]
+fn disabled() {
+    let global = generate(gl_generator::GlobalGenerator, false, Version::new(1, 0));
+    assert!(!global.contains("PrimitiveType"));
+    assert!(global.contains("pub unsafe fn DrawArrays(mode: types::GLenum"));
+}
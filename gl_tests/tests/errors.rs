@@ -0,0 +1,65 @@
+//! Checks that malformed registries and unknown types are reported as errors.
+
+extern crate gl_generator;
+
+use gl_generator::{Error, Fallbacks, Version};
+use gl_generator::registry::{Ns, Registry};
+
+const MALFORMED: &'static [u8] = include_bytes!("fixtures/malformed.xml");
+const UNEXPECTED_ELEMENT: &'static [u8] = include_bytes!("fixtures/unexpected_element.xml");
+const UNKNOWN_TYPE: &'static [u8] = include_bytes!("fixtures/unknown_type.xml");
+
+fn generate(extensions: Vec<String>) -> Result<Vec<String>, Error> {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(gl_generator::GlobalGenerator, Ns::Gl, Fallbacks::All,
+                                    UNKNOWN_TYPE, extensions, Some(Version::new(1, 0)), "core",
+                                    &mut dest)
+}
+
+#[test]
+fn malformed_xml() {
+    match Registry::from_xml(MALFORMED, Ns::Gl, None) {
+        Err(Error::Xml { row, col, ref msg }) => {
+            assert_eq!((row, col), (4, 10));
+            assert!(msg.contains("registry != commands"), "{}", msg);
+        },
+        _ => panic!("the registry isn't well-formed"),
+    }
+    // the position is displayed from 1
+    let err = Registry::from_xml(MALFORMED, Ns::Gl, None).err().unwrap();
+    assert!(err.to_string().starts_with("XML error at 5:11: "));
+}
+
+#[test]
+fn unexpected_element() {
+    match Registry::from_xml(UNEXPECTED_ELEMENT, Ns::Gl, None) {
+        Err(Error::UnexpectedElement { ref path, ref found }) => {
+            assert_eq!(path, "registry/commands");
+            assert_eq!(found, "element <macro>");
+        },
+        _ => panic!("`<macro>` isn't a command"),
+    }
+}
+
+#[test]
+fn unknown_param_type() {
+    match generate(vec!["GL_EXT_weird".to_string()]) {
+        Err(Error::UnknownType { ref ty, ref cmd, ref param }) => {
+            assert_eq!(ty, "GLweird");
+            assert_eq!(cmd, "Foo");
+            assert_eq!(param.as_ref().map(|p| &p[..]), Some("weird"));
+        },
+        _ => panic!("`GLweird` has no Rust equivalent"),
+    }
+}
+
+#[test]
+fn unknown_return_type() {
+    match generate(vec![]) {
+        Err(Error::UnknownType { ref ty, ref cmd, param: None }) => {
+            assert_eq!(ty, "GLweird");
+            assert_eq!(cmd, "GetWeird");
+        },
+        _ => panic!("`GLweird` has no Rust equivalent"),
+    }
+}
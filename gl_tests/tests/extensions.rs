@@ -0,0 +1,108 @@
+//! Checks the selection of extensions by name, pattern and vendor.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, ExtensionSelector, Profile, Version};
+use gl_generator::registry::Ns;
+
+const XML: &'static [u8] = include_bytes!("fixtures/extensions.xml");
+
+fn select(extensions: ExtensionSelector) -> Result<Vec<String>, Error> {
+    let registry = try!(common::filter(XML, Ns::Gl, Some(Version::new(1, 0)), Some(Profile::Compatibility),
+                                       extensions));
+    Ok(registry.extensions.iter().map(|e| e.name.clone()).collect())
+}
+
+fn names(names: &[&str]) -> ExtensionSelector {
+    names.iter().map(|name| name.to_string()).collect::<Vec<_>>().into()
+}
+
+#[test]
+fn by_name() {
+    assert_eq!(select(names(&["GL_NV_fence", "GL_ARB_sync"])).unwrap(),
+               ["GL_ARB_sync", "GL_NV_fence"]);
+    assert!(select(ExtensionSelector::default()).unwrap().is_empty());
+}
+
+#[test]
+fn all_supported() {
+    let selector = ExtensionSelector { all_supported: true, .. ExtensionSelector::default() };
+    assert_eq!(select(selector).unwrap(),
+               ["GL_ARB_sync", "GL_ARB_debug_output", "GL_EXT_texture", "GL_NV_fence", "GL_KHR_debug"]);
+}
+
+#[test]
+fn by_pattern() {
+    assert_eq!(select(names(&["GL_ARB_*"])).unwrap(), ["GL_ARB_sync", "GL_ARB_debug_output"]);
+    assert_eq!(select(names(&["*_debug"])).unwrap(), ["GL_KHR_debug"]);
+    assert_eq!(select(names(&["*debug*"])).unwrap(), ["GL_ARB_debug_output", "GL_KHR_debug"]);
+    assert_eq!(select(names(&["GL_??_fence"])).unwrap(), ["GL_NV_fence"]);
+    assert!(select(names(&["GL_?_fence"])).unwrap().is_empty());
+}
+
+#[test]
+fn pattern_skips_unsupported_extensions() {
+    // `GL_OES_mapbuffer` doesn't support gl
+    assert_eq!(select(names(&["GL_*_*"])).unwrap(),
+               ["GL_ARB_sync", "GL_ARB_debug_output", "GL_EXT_texture", "GL_NV_fence", "GL_KHR_debug"]);
+}
+
+#[test]
+fn by_vendor() {
+    let selector = ExtensionSelector {
+        vendors: vec!["ARB".to_string(), "NV".to_string()],
+        .. ExtensionSelector::default()
+    };
+    assert_eq!(select(selector).unwrap(), ["GL_ARB_sync", "GL_ARB_debug_output", "GL_NV_fence"]);
+}
+
+#[test]
+fn exclude() {
+    let selector = ExtensionSelector {
+        all_supported: true,
+        exclude: vec!["*_debug*".to_string(), "GL_ARB_sync".to_string()],
+        exclude_vendors: vec!["NV".to_string()],
+        .. ExtensionSelector::default()
+    };
+    assert_eq!(select(selector).unwrap(), ["GL_EXT_texture"]);
+
+    // the excludes take precedence over the names
+    let selector = ExtensionSelector { exclude: vec!["GL_ARB_*".to_string()], .. names(&["GL_ARB_sync"]) };
+    assert!(select(selector).unwrap().is_empty());
+}
+
+#[test]
+fn empty_pattern() {
+    let selector = ExtensionSelector {
+        all_supported: true,
+        exclude: vec!["".to_string()],
+        .. ExtensionSelector::default()
+    };
+    assert_eq!(select(selector).unwrap().len(), 5);
+
+    match select(names(&[""])) {
+        Err(Error::UnknownExtension(ref name)) => assert_eq!(name, ""),
+        _ => panic!("the empty name is not an extension"),
+    }
+}
+
+#[test]
+fn unknown_extension() {
+    match select(names(&["GL_ARB_sync", "GL_ARB_missing"])) {
+        Err(Error::UnknownExtension(ref name)) => assert_eq!(name, "GL_ARB_missing"),
+        _ => panic!("`GL_ARB_missing` is not in the registry"),
+    }
+}
+
+#[test]
+fn unsupported_extension() {
+    match select(names(&["GL_OES_mapbuffer"])) {
+        Err(Error::UnsupportedExtension { ref name, ref api }) => {
+            assert_eq!(name, "GL_OES_mapbuffer");
+            assert_eq!(api, "gl");
+        },
+        _ => panic!("`GL_OES_mapbuffer` doesn't support gl"),
+    }
+}
@@ -0,0 +1,110 @@
+//! Checks that commands fall back to all the commands they are aliased with.
+
+extern crate gl_generator;
+
+use gl_generator::{FallbackPolicy, Fallbacks, Version};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/fallbacks.xml");
+const VENDOR_XML: &'static [u8] = include_bytes!("fixtures/fallbacks_vendor.xml");
+
+fn generate<G>(generator: G, fallbacks: Fallbacks) -> String where G: gl_generator::generators::Generator {
+    let mut dest = Vec::new();
+    let extensions = vec!["GL_EXT_foo".to_string()];
+    gl_generator::generate_bindings(generator, Ns::Gl, fallbacks, XML, extensions,
+                                    Some(Version::new(1, 0)), "core", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn transitive_and_bidirectional() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    assert_eq!(registry.fallbacks("Foo"), ["FooARB", "FooEXT"]);
+    assert_eq!(registry.fallbacks("FooARB"), ["FooEXT", "Foo"]);
+    assert_eq!(registry.fallbacks("FooEXT"), ["FooARB", "Foo"]);
+    assert!(registry.fallbacks("Bar").is_empty());
+}
+
+#[test]
+fn merged_aliases() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    let vendor = Registry::from_xml(VENDOR_XML, Ns::Gl, None).unwrap();
+    let registry = registry.merge(vendor).unwrap();
+    assert_eq!(registry.fallbacks("Foo"), ["FooARB", "FooEXT", "FooOES"]);
+    assert_eq!(registry.fallbacks("FooOES"), ["FooEXT", "FooARB", "Foo"]);
+}
+
+#[test]
+fn generators() {
+    let global = generate(gl_generator::GlobalGenerator, Fallbacks::All);
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFooEXT", &["glFooARB", "glFoo"])"#));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFoo", &["glFooARB", "glFooEXT"])"#));
+
+    let struct_ = generate(gl_generator::StructGenerator, Fallbacks::All);
+    assert!(struct_.contains(r#"metaloadfn("glFooEXT", &["glFooARB", "glFoo"])"#));
+    assert!(struct_.contains(r#"metaloadfn("glBar", &[])"#));
+}
+
+#[test]
+fn no_fallbacks() {
+    let global = generate(gl_generator::GlobalGenerator, Fallbacks::None);
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFooEXT", &[])"#));
+}
+
+#[test]
+fn policy_order() {
+    let policy = FallbackPolicy { prefer: vec!["EXT".to_string()], .. FallbackPolicy::default() };
+    let global = generate(gl_generator::GlobalGenerator, Fallbacks::Policy(policy));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFoo", &["glFooEXT", "glFooARB"])"#));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFooEXT", &["glFooARB", "glFoo"])"#));
+}
+
+#[test]
+fn policy_deny() {
+    let policy = FallbackPolicy { deny_vendors: vec!["ARB".to_string()], .. FallbackPolicy::default() };
+    let global = generate(gl_generator::GlobalGenerator, Fallbacks::Policy(policy));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFoo", &["glFooEXT"])"#));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFooEXT", &["glFoo"])"#));
+
+    let policy = FallbackPolicy { deny: vec!["Foo".to_string()], .. FallbackPolicy::default() };
+    let global = generate(gl_generator::GlobalGenerator, Fallbacks::Policy(policy));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFooEXT", &["glFooARB"])"#));
+}
+
+#[test]
+fn policy_deny_exact_vendor() {
+    let deny = |vendors: &[&str]| {
+        let vendors = vendors.iter().map(|v| v.to_string()).collect();
+        let policy = FallbackPolicy { deny_vendors: vendors, .. FallbackPolicy::default() };
+        generate(gl_generator::GlobalGenerator, Fallbacks::Policy(policy))
+    };
+
+    let global = deny(&["XT", "RB", "ES", "DOES"]);
+    assert!(global.contains(r#"metaloadfn(loadfn, "glFoo", &["glFooARB", "glFooEXT"])"#));
+    assert!(global.contains(r#"metaloadfn(loadfn, "glTexImage3D", &["glTexImage3DOES"])"#));
+
+    let global = deny(&["OES"]);
+    assert!(global.contains(r#"metaloadfn(loadfn, "glTexImage3D", &[])"#));
+}
+
+#[test]
+fn policy_extra() {
+    let mut policy = FallbackPolicy::default();
+    policy.extra.insert("Bar".to_string(), vec!["BarANGLE".to_string()]);
+    policy.extra.insert("Foo".to_string(), vec!["FooANGLE".to_string()]);
+    policy.deny_vendors = vec!["ANGLE".to_string()];
+    let struct_ = generate(gl_generator::StructGenerator, Fallbacks::Policy(policy));
+    assert!(struct_.contains(r#"metaloadfn("glBar", &["glBarANGLE"])"#));
+    assert!(struct_.contains(r#"metaloadfn("glFoo", &["glFooARB", "glFooEXT", "glFooANGLE"])"#));
+}
+
+#[test]
+fn policy_extra_vendor() {
+    // `TexImage2D` has no aliases, so the vendors of its extra fallbacks come from their names
+    let mut policy = FallbackPolicy::default();
+    policy.extra.insert("TexImage2D".to_string(),
+                        vec!["TexImage2DEXT".to_string(), "TexImage2DOES".to_string()]);
+    policy.prefer = vec!["OES".to_string()];
+    let struct_ = generate(gl_generator::StructGenerator, Fallbacks::Policy(policy));
+    assert!(struct_.contains(r#"metaloadfn("glTexImage2D", &["glTexImage2DOES", "glTexImage2DEXT"])"#));
+}
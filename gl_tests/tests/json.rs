@@ -0,0 +1,99 @@
+//! Checks that a registry written as JSON reads back the same, and the errors of invalid JSON.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, Profile, Version, JSON_SCHEMA_VERSION};
+use gl_generator::registry::{Len, Ns, Registry};
+
+const PROFILES: &'static [u8] = include_bytes!("fixtures/profiles.xml");
+const ALIASES: &'static [u8] = include_bytes!("fixtures/aliases.xml");
+const PARAMS: &'static [u8] = include_bytes!("fixtures/params.xml");
+
+fn filter(xml: &[u8], ns: Ns, version: Version, profile: Option<Profile>) -> Registry {
+    common::filter(xml, ns, Some(version), profile, Default::default()).unwrap()
+}
+
+fn to_json(registry: &Registry) -> String {
+    let mut json = Vec::new();
+    registry.write_json(&mut json).unwrap();
+    String::from_utf8(json).unwrap()
+}
+
+/// Reads `registry` back from its JSON, and checks that it writes the same JSON again.
+fn round_trip(registry: &Registry) -> Registry {
+    let json = to_json(registry);
+    let reloaded = Registry::from_json(json.as_bytes()).unwrap();
+    assert_eq!(to_json(&reloaded), json);
+    reloaded
+}
+
+fn json_error(json: &str) -> (String, String) {
+    match Registry::from_json(json.as_bytes()) {
+        Err(Error::Json { path, msg }) => (path, msg),
+        Err(err) => panic!("unexpected error: {}", err),
+        Ok(_) => panic!("the JSON is invalid"),
+    }
+}
+
+/// The JSON of a small registry, with `from` replaced with `to`.
+fn patched(from: &str, to: &str) -> String {
+    let json = to_json(&filter(PROFILES, Ns::Gl, Version::new(1, 0), Some(Profile::Core)));
+    assert!(json.contains(from));
+    json.replacen(from, to, 1)
+}
+
+#[test]
+fn round_trip_filtered() {
+    let registry = filter(PROFILES, Ns::Gl, Version::new(1, 0), Some(Profile::Core));
+    let reloaded = round_trip(&registry);
+    assert_eq!(reloaded.cmds.len(), registry.cmds.len());
+    assert_eq!(reloaded.features.len(), registry.features.len());
+    assert_eq!(reloaded.removed_from_core("Begin"), Some(Version::new(3, 2)));
+    assert_eq!(reloaded.introduced_in("Begin"), ["GL_VERSION_1_0"]);
+}
+
+#[test]
+fn round_trip_equivalent_enums() {
+    let registry = filter(ALIASES, Ns::Gles2, Version::new(2, 0), None);
+    let reloaded = round_trip(&registry);
+    assert_eq!(reloaded.equivalent_enums.len(), registry.equivalent_enums.len());
+}
+
+#[test]
+fn round_trip_unfiltered() {
+    let registry = Registry::from_xml(PARAMS, Ns::Gl, None).unwrap();
+    let reloaded = round_trip(&registry);
+    let cmd = reloaded.cmds.iter().find(|c| c.proto.ident == "UniformMatrix2fv").unwrap();
+    assert_eq!(cmd.params[1].len, Some(Len::Param { name: "count".to_string(), factor: 4 }));
+}
+
+#[test]
+fn schema_version() {
+    let json = patched(&format!("\"schema\": {}", JSON_SCHEMA_VERSION), "\"schema\": 1");
+    let (path, msg) = json_error(&json);
+    assert_eq!(path, "schema");
+    assert_eq!(msg, format!("unsupported schema version, expected {}", JSON_SCHEMA_VERSION));
+}
+
+#[test]
+fn error_paths() {
+    let (path, msg) = json_error(&patched("\"number\": \"1.0\"", "\"number\": \"one\""));
+    assert_eq!(path, "features[0].number");
+    assert_eq!(msg, "expected a version of the form `major.minor`");
+
+    let (path, msg) = json_error(&patched("\"ident\": \"mode\"", "\"ident\": 1"));
+    assert_eq!(path, "cmds[0].params[0].ident");
+    assert_eq!(msg, "expected a string");
+
+    let (path, msg) = json_error(&patched("\"ident\": \"mode\",", ""));
+    assert_eq!(path, "cmds[0].params[0]");
+    assert_eq!(msg, "missing member `ident`");
+}
+
+#[test]
+fn syntax_error() {
+    let (path, _) = json_error("{\"schema\": ");
+    assert_eq!(path, "");
+}
@@ -0,0 +1,106 @@
+//! Checks the merging of a vendor registry into another one.
+
+extern crate gl_generator;
+
+use gl_generator::Error;
+use gl_generator::registry::{Ns, Registry};
+
+const BASE: &'static [u8] = include_bytes!("fixtures/merge_base.xml");
+const VENDOR: &'static [u8] = include_bytes!("fixtures/merge_vendor.xml");
+
+fn parse(xml: &[u8]) -> Registry {
+    Registry::from_xml(xml, Ns::Gl, None).unwrap()
+}
+
+/// Merges the vendor registry, after replacing `from` with `to` in it.
+fn merge_patched(from: &str, to: &str) -> Result<Registry, Error> {
+    let vendor = String::from_utf8(VENDOR.to_vec()).unwrap();
+    assert!(vendor.contains(from));
+    parse(BASE).merge(parse(vendor.replace(from, to).as_bytes()))
+}
+
+#[test]
+fn identical_definitions() {
+    let merged = parse(BASE).merge(parse(VENDOR)).unwrap();
+
+    let cmds: Vec<&str> = merged.cmds.iter().map(|c| &c.proto.ident[..]).collect();
+    assert_eq!(cmds, ["Clear", "VendorClearX"]);
+    let enums: Vec<&str> = merged.enums.iter().map(|e| &e.ident[..]).collect();
+    assert_eq!(enums, ["COLOR_BUFFER_BIT", "VENDOR_BUFFER_BIT"]);
+    let types: Vec<&str> = merged.types.iter().map(|t| &t.name[..]).collect();
+    assert_eq!(types, ["GLenum", "GLbitfield"]);
+
+    assert_eq!(merged.groups.len(), 1);
+    assert_eq!(merged.groups[0].enums, ["COLOR_BUFFER_BIT", "VENDOR_BUFFER_BIT"]);
+    assert_eq!(merged.features.len(), 1);
+    assert_eq!(merged.extensions.len(), 1);
+}
+
+#[test]
+fn merge_with_itself() {
+    let merged = parse(BASE).merge(parse(BASE)).unwrap();
+    assert_eq!(merged.cmds.len(), 1);
+    assert_eq!(merged.enums.len(), 1);
+    assert_eq!(merged.types.len(), 2);
+}
+
+#[test]
+fn conflicting_enum() {
+    match merge_patched(r#"value="0x4000""#, r#"value="0x4001""#) {
+        Err(Error::ConflictingEnum { ref name, ref values }) => {
+            assert_eq!(name, "COLOR_BUFFER_BIT");
+            assert_eq!(values, &["0x4000", "0x4001"]);
+        },
+        _ => panic!("the two values of `COLOR_BUFFER_BIT` conflict"),
+    }
+}
+
+#[test]
+fn conflicting_command() {
+    let from = "<proto>void <name>glClear</name></proto>";
+    match merge_patched(from, "<proto><ptype>GLenum</ptype> <name>glClear</name></proto>") {
+        Err(Error::ConflictingCommand { ref name, ref signatures }) => {
+            assert_eq!(name, "Clear");
+            assert_eq!(signatures, &["void Clear(GLbitfield)", "GLenum Clear(GLbitfield)"]);
+        },
+        _ => panic!("the two signatures of `Clear` conflict"),
+    }
+}
+
+#[test]
+fn conflicting_type() {
+    match merge_patched("typedef unsigned int <name>GLbitfield</name>;",
+                        "typedef int <name>GLbitfield</name>;") {
+        Err(Error::ConflictingType { ref name }) => assert_eq!(name, "GLbitfield"),
+        _ => panic!("the two definitions of `GLbitfield` conflict"),
+    }
+}
+
+#[test]
+fn repeated_definitions() {
+    let enm = r#"<enum value="0x8000" name="GL_VENDOR_BUFFER_BIT"/>"#;
+    let repeated = format!("{}\n{}", enm, enm);
+    let merged = merge_patched(enm, &repeated).unwrap();
+    let enums: Vec<&str> = merged.enums.iter().map(|e| &e.ident[..]).collect();
+    assert_eq!(enums, ["COLOR_BUFFER_BIT", "VENDOR_BUFFER_BIT"]);
+
+    let cmd = "<command>
+            <proto>void <name>glVendorClearX</name></proto>
+            <param group=\"ClearBufferMask\"><ptype>GLbitfield</ptype> <name>mask</name></param>
+        </command>";
+    let merged = merge_patched(cmd, &format!("{}\n{}", cmd, cmd)).unwrap();
+    let cmds: Vec<&str> = merged.cmds.iter().map(|c| &c.proto.ident[..]).collect();
+    assert_eq!(cmds, ["Clear", "VendorClearX"]);
+}
+
+#[test]
+fn repeated_conflicting_definitions() {
+    let enm = r#"<enum value="0x8000" name="GL_VENDOR_BUFFER_BIT"/>"#;
+    match merge_patched(enm, &format!("{}\n{}", enm, enm.replace("0x8000", "0x8001"))) {
+        Err(Error::ConflictingEnum { ref name, ref values }) => {
+            assert_eq!(name, "VENDOR_BUFFER_BIT");
+            assert_eq!(values, &["0x8000", "0x8001"]);
+        },
+        _ => panic!("the two values of `VENDOR_BUFFER_BIT` conflict"),
+    }
+}
@@ -0,0 +1,88 @@
+//! Uses the bindings of `MockGenerator` without an OpenGL context.
+
+extern crate libc;
+extern crate gl_common;
+
+mod gl {
+    include!(concat!(env!("OUT_DIR"), "/test_mock.rs"));
+}
+
+use gl::types::*;
+use gl::Call;
+
+#[test]
+fn records_calls() {
+    let gl = gl::Gl::new();
+    unsafe {
+        gl.Clear(ClearBufferMask::COLOR_BUFFER_BIT | ClearBufferMask::DEPTH_BUFFER_BIT);
+        gl.DrawArrays(PrimitiveType::TRIANGLES, 0, 3);
+        gl.Flush();
+    }
+    assert_eq!(gl.calls(), vec![
+        Call::Clear { mask: ClearBufferMask::COLOR_BUFFER_BIT | ClearBufferMask::DEPTH_BUFFER_BIT },
+        Call::DrawArrays { mode: PrimitiveType::TRIANGLES, first: 0, count: 3 },
+        Call::Flush,
+    ]);
+
+    assert_eq!(gl.take_calls().len(), 3);
+    assert!(gl.calls().is_empty());
+}
+
+#[test]
+fn returns_zero() {
+    let gl = gl::Gl::new();
+    assert_eq!(unsafe { gl.GetError() }, gl::NO_ERROR);
+    assert!(unsafe { gl.GetString(StringName::VERSION) }.is_null());
+}
+
+#[test]
+fn queued_returns() {
+    let gl = gl::Gl::new();
+    gl.GetError.push_return(gl::INVALID_ENUM);
+    gl.GetError.push_return(gl::OUT_OF_MEMORY);
+    unsafe {
+        assert_eq!(gl.GetError(), gl::INVALID_ENUM);
+        assert_eq!(gl.GetError(), gl::OUT_OF_MEMORY);
+        assert_eq!(gl.GetError(), gl::NO_ERROR);
+    }
+    assert_eq!(gl.calls(), vec![Call::GetError, Call::GetError, Call::GetError]);
+}
+
+#[test]
+fn handlers() {
+    let gl = gl::Gl::new();
+    let mut next = 1;
+    gl.GenBuffers.set_handler(Box::new(move |n: GLsizei, buffers: *mut GLuint| {
+        for i in 0 .. n as isize {
+            unsafe { *buffers.offset(i) = next; }
+            next += 1;
+        }
+    }));
+
+    let mut buffers = [0; 3];
+    unsafe {
+        gl.GenBuffers(2, buffers.as_mut_ptr());
+        gl.GenBuffers(1, buffers[2..].as_mut_ptr());
+    }
+    assert_eq!(buffers, [1, 2, 3]);
+
+    gl.GenBuffers.reset();
+    unsafe { gl.GenBuffers(3, buffers.as_mut_ptr()) };
+    assert_eq!(buffers, [1, 2, 3]);
+    assert_eq!(gl.calls().len(), 3);
+}
+
+#[test]
+fn loaded() {
+    struct NoLoader;
+    impl gl_common::GlFunctionsSource for NoLoader {
+        fn get_proc_addr(&self, _: &str) -> *const libc::c_void {
+            std::ptr::null()
+        }
+    }
+
+    let gl = gl::Gl::load(&NoLoader);
+    assert!(gl.DrawArrays.is_loaded());
+    let gl = gl::Gl::load_with(|_| std::ptr::null());
+    assert!(gl.GenBuffers.is_loaded());
+}
@@ -0,0 +1,29 @@
+//! Checks that the mock only returns zero for the types where it is a valid value.
+
+extern crate gl_generator;
+
+use gl_generator::{Fallbacks, MockGenerator, Version};
+use gl_generator::registry::Ns;
+
+const XML: &'static [u8] = include_bytes!("fixtures/mock_returns.xml");
+
+fn generate() -> String {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(MockGenerator, Ns::Gl, Fallbacks::None, XML, vec![],
+                                    Some(Version::new(1, 0)), "core", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn zeroed_returns() {
+    let mock = generate();
+    for name in &["GetError", "FenceSync", "GetName"] {
+        assert!(!mock.contains(&format!("no return value scripted for {}", name)), "{}", name);
+    }
+}
+
+#[test]
+fn function_pointer_returns() {
+    let mock = generate();
+    assert!(mock.contains(r#"panic!("no return value scripted for GetProcAddress")"#));
+}
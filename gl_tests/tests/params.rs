@@ -0,0 +1,50 @@
+//! Checks the parsing of the `len`, `class` and `kind` attributes of command parameters.
+
+extern crate gl_generator;
+
+use gl_generator::registry::{Binding, Len, Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/params.xml");
+
+fn param<'a>(registry: &'a Registry, cmd: &str, param: &str) -> &'a Binding {
+    let cmd = registry.cmds.iter().find(|c| c.proto.ident == cmd).unwrap();
+    cmd.params.iter().find(|p| p.ident == param).unwrap()
+}
+
+#[test]
+fn parse_len() {
+    assert_eq!(Len::parse("16"), Len::Constant(16));
+    assert_eq!(Len::parse("count"), Len::Param { name: "count".to_string(), factor: 1 });
+    assert_eq!(Len::parse("count*2"), Len::Param { name: "count".to_string(), factor: 2 });
+    assert_eq!(Len::parse("COMPSIZE(format,type)"),
+               Len::CompSize(vec!["format".to_string(), "type".to_string()]));
+    assert_eq!(Len::parse("COMPSIZE()"), Len::CompSize(vec![]));
+    assert_eq!(Len::parse("null-terminated"), Len::NullTerminated);
+    assert_eq!(Len::parse("count*n"), Len::Other("count*n".to_string()));
+    assert_eq!(Len::parse("(n+1)"), Len::Other("(n+1)".to_string()));
+}
+
+#[test]
+fn display_len() {
+    for &len in ["16", "count", "count*2", "COMPSIZE(format,type)", "null-terminated", "(n+1)"].iter() {
+        assert_eq!(Len::parse(len).to_string(), len);
+    }
+}
+
+#[test]
+fn params_from_the_registry() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    assert_eq!(param(&registry, "TexImage1D", "pixels").len,
+               Some(Len::CompSize(vec!["format".to_string(), "type".to_string()])));
+    assert_eq!(param(&registry, "TexImage1D", "format").len, None);
+    assert_eq!(param(&registry, "UniformMatrix2fv", "value").len,
+               Some(Len::Param { name: "count".to_string(), factor: 4 }));
+
+    let name = param(&registry, "ObjectLabel", "name");
+    assert_eq!(name.class, Some("buffer".to_string()));
+    assert_eq!(name.kind, Some("String".to_string()));
+    assert_eq!(name.len, None);
+    let label = param(&registry, "ObjectLabel", "label");
+    assert_eq!(label.len, Some(Len::Param { name: "length".to_string(), factor: 1 }));
+    assert_eq!(label.class, None);
+}
@@ -0,0 +1,111 @@
+//! Checks how the requested profile selects the `<require>` and `<remove>` blocks of a registry.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, ExtensionSelector, Fallbacks, Profile, Version};
+use gl_generator::registry::{Ns, Registry};
+
+use common::{cmds, enums};
+
+const XML: &'static [u8] = include_bytes!("fixtures/profiles.xml");
+
+fn filter(ns: Ns, version: Version, profile: Option<Profile>) -> Result<Registry, Error> {
+    common::filter(XML, ns, Some(version), profile, ExtensionSelector::default())
+}
+
+#[test]
+fn parse_profile() {
+    assert_eq!("core".parse::<Profile>().unwrap(), Profile::Core);
+    assert_eq!("compatibility".parse::<Profile>().unwrap(), Profile::Compatibility);
+    match "compat".parse::<Profile>() {
+        Err(Error::InvalidProfile(ref profile)) => assert_eq!(profile, "compat"),
+        _ => panic!("`compat` is not a profile"),
+    }
+}
+
+#[test]
+fn removes_do_not_apply_before_their_version() {
+    let registry = filter(Ns::Gl, Version::new(1, 0), Some(Profile::Core)).unwrap();
+    assert_eq!(cmds(&registry), ["Begin", "Clear", "End", "Rotatef"]);
+}
+
+#[test]
+fn core_applies_every_remove_block() {
+    let registry = filter(Ns::Gl, Version::new(3, 2), Some(Profile::Core)).unwrap();
+    assert_eq!(cmds(&registry), ["Clear", "FenceSync"]);
+    assert_eq!(enums(&registry), ["COLOR_BUFFER_BIT", "SYNC_GPU_COMMANDS_COMPLETE"]);
+    assert_eq!(registry.removed_in("Begin"), ["GL_VERSION_3_2"]);
+    assert_eq!(registry.removed_in("Rotatef"), ["GL_VERSION_3_2"]);
+}
+
+#[test]
+fn compatibility_ignores_core_removes() {
+    let registry = filter(Ns::Gl, Version::new(3, 2), Some(Profile::Compatibility)).unwrap();
+    assert_eq!(cmds(&registry), ["Begin", "Clear", "End", "FenceSync", "Rotatef"]);
+    assert_eq!(enums(&registry),
+               ["COLOR_BUFFER_BIT", "QUADS", "STACK_OVERFLOW", "SYNC_GPU_COMMANDS_COMPLETE"]);
+    assert!(registry.removed_in("Begin").is_empty());
+}
+
+#[test]
+fn later_version_restores_removed_enum() {
+    let core = filter(Ns::Gl, Version::new(4, 3), Some(Profile::Core)).unwrap();
+    assert_eq!(enums(&core), ["COLOR_BUFFER_BIT", "STACK_OVERFLOW", "SYNC_GPU_COMMANDS_COMPLETE"]);
+    assert_eq!(cmds(&core), ["Clear", "FenceSync", "PushDebugGroup"]);
+
+    let compatibility = filter(Ns::Gl, Version::new(4, 3), Some(Profile::Compatibility)).unwrap();
+    assert_eq!(cmds(&compatibility),
+               ["Begin", "Clear", "End", "FenceSync", "PushDebugGroup", "Rotatef"]);
+}
+
+#[test]
+fn core_only_selects_glcore_extensions() {
+    let all = || ExtensionSelector { all_supported: true, .. ExtensionSelector::default() };
+    let version = Some(Version::new(3, 2));
+    let core = common::filter(XML, Ns::Gl, version, Some(Profile::Core), all()).unwrap();
+    let names: Vec<&str> = core.extensions.iter().map(|e| &e.name[..]).collect();
+    assert_eq!(names, ["GL_ARB_sync"]);
+
+    let compatibility = common::filter(XML, Ns::Gl, version, Some(Profile::Compatibility), all()).unwrap();
+    let names: Vec<&str> = compatibility.extensions.iter().map(|e| &e.name[..]).collect();
+    assert_eq!(names, ["GL_ARB_sync", "GL_ARB_compatibility"]);
+}
+
+#[test]
+fn gl_requires_a_profile() {
+    match filter(Ns::Gl, Version::new(3, 2), None) {
+        Err(Error::ProfileRequired(ref api)) => assert_eq!(api, "gl"),
+        _ => panic!("filtering gl without a profile must fail"),
+    }
+}
+
+#[test]
+fn gles_ignores_profiles() {
+    for &profile in [None, Some(Profile::Core), Some(Profile::Compatibility)].iter() {
+        let registry = filter(Ns::Gles2, Version::new(2, 0), profile).unwrap();
+        assert_eq!(cmds(&registry), ["Clear", "FenceSync"]);
+    }
+}
+
+#[test]
+fn generate_bindings_rejects_unknown_profiles() {
+    let mut dest = Vec::new();
+    match gl_generator::generate_bindings(gl_generator::StructGenerator, Ns::Gl, Fallbacks::All,
+                                          XML, vec![], Some(Version::new(3, 2)), "compat",
+                                          &mut dest) {
+        Err(Error::InvalidProfile(ref profile)) => assert_eq!(profile, "compat"),
+        _ => panic!("`compat` is not a profile"),
+    }
+}
+
+#[test]
+fn generate_bindings_ignores_the_profile_of_other_apis() {
+    for &profile in ["", "compat", "core"].iter() {
+        let mut dest = Vec::new();
+        gl_generator::generate_bindings(gl_generator::StructGenerator, Ns::Gles2, Fallbacks::All,
+                                        XML, vec![], Some(Version::new(2, 0)), profile,
+                                        &mut dest).unwrap();
+    }
+}
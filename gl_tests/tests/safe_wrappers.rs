@@ -0,0 +1,98 @@
+//! Checks the safe methods that `SafeWrapperGenerator` adds to the struct bindings.
+
+extern crate gl_generator;
+extern crate khronos_api;
+
+use gl_generator::{Fallbacks, Version};
+use gl_generator::registry::Ns;
+
+const XML: &'static [u8] = include_bytes!("fixtures/safe_wrappers.xml");
+
+fn generate() -> String {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(gl_generator::SafeWrapperGenerator, Ns::Gl, Fallbacks::All, XML,
+                                    vec![], Some(Version::new(1, 0)), "core", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+fn generate_gl() -> String {
+    let mut dest = Vec::new();
+    gl_generator::generate_bindings(gl_generator::SafeWrapperGenerator, Ns::Gl, Fallbacks::All,
+                                    khronos_api::GL_XML, vec![], Some(Version::new(4, 5)),
+                                    "compatibility", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn unsafe_methods() {
+    let safe = generate();
+    // the bindings of the struct generator are still there
+    assert!(safe.contains("pub struct Gl {"));
+    assert!(safe.contains("pub unsafe fn GenBuffers(&self, n: types::GLsizei, buffers: *mut types::GLuint) -> ()"));
+}
+
+#[test]
+fn slices() {
+    let safe = generate();
+    assert!(safe.contains("/// Safe version of `GenBuffers`."));
+    assert!(safe.contains(
+        "pub fn gen_buffers(&self, buffers: &mut [types::GLuint]) -> () {  \
+         unsafe { self.GenBuffers(buffers.len() as types::GLsizei, buffers.as_mut_ptr()) } }"));
+    assert!(safe.contains(
+        "pub fn buffer_data(&self, target: types::GLenum, data: &[u8], usage: types::GLenum) -> () {  \
+         unsafe { self.BufferData(target, data.len() as types::GLsizeiptr, data.as_ptr() as *const _, usage) } }"));
+    assert!(safe.contains(
+        "pub fn uniform_matrix4fv(&self, location: types::GLint, transpose: types::GLboolean, \
+         value: &[types::GLfloat]) -> () {  unsafe { self.UniformMatrix4fv(location, \
+         (value.len() / 16) as types::GLsizei, transpose, value.as_ptr()) } }"));
+}
+
+#[test]
+fn references() {
+    let safe = generate();
+    assert!(safe.contains("pub fn color3fv(&self, v: &[types::GLfloat; 3]) -> () {  unsafe { self.Color3fv(v.as_ptr()) } }"));
+    assert!(safe.contains(
+        "pub fn get_shader_info_log(&self, shader: types::GLuint, length: &mut types::GLsizei, \
+         infoLog: &mut [types::GLchar]) -> () {  unsafe { self.GetShaderInfoLog(shader, \
+         infoLog.len() as types::GLsizei, length, infoLog.as_mut_ptr()) } }"));
+}
+
+#[test]
+fn strings() {
+    let safe = generate();
+    assert!(safe.contains(
+        "pub fn get_uniform_location(&self, program: types::GLuint, name: &::std::ffi::CStr) -> types::GLint"));
+    assert!(safe.contains("pub fn get_string(&self, name: types::GLenum) -> Option<&::std::ffi::CStr>"));
+    assert!(safe.contains("pub fn shader_source(&self, shader: types::GLuint, string: &[&str]) -> ()"));
+    assert!(safe.contains(
+        "self.ShaderSource(shader, string.len() as types::GLsizei, string_ptrs.as_ptr(), string_lengths.as_ptr())"));
+}
+
+#[test]
+fn unsafe_only() {
+    let safe = generate();
+    assert!(!safe.contains("pub fn map_buffer("));
+    assert!(!safe.contains("pub fn vertex_attrib_pointer("));
+    assert!(!safe.contains("pub fn delete_sync("));
+    // the pointers are kept after the call
+    assert!(!safe.contains("pub fn vertex_attrib_lpointer("));
+    assert!(!safe.contains("pub fn feedback_buffer("));
+    assert!(!safe.contains("pub fn select_buffer("));
+    // the indices can be an offset into a buffer
+    assert!(!safe.contains("pub fn draw_elements_instanced_base_instance("));
+    // `count` isn't a number of bytes
+    assert!(!safe.contains("pub fn clear_buffer_data("));
+}
+
+#[test]
+fn unsafe_only_gl() {
+    let safe = generate_gl();
+    assert!(safe.contains("pub fn gen_buffers("));
+    assert!(safe.contains("pub fn buffer_data("));
+    for name in &["draw_elements(", "draw_elements_instanced_base_instance(",
+                  "draw_elements_instanced_base_vertex_base_instance(", "draw_range_elements(",
+                  "multi_draw_elements(", "vertex_attrib_pointer(", "vertex_attrib_lpointer(",
+                  "color_pointer(", "feedback_buffer(", "select_buffer(", "debug_message_callback("] {
+        assert!(!safe.contains(&format!("pub fn {}", name)), "{}", name);
+    }
+}
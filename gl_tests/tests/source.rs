@@ -0,0 +1,55 @@
+//! Checks reading the registry from a path, and the `GL_GENERATOR_XML_DIR` override.
+
+extern crate gl_generator;
+
+use std::env;
+use std::path::PathBuf;
+
+use gl_generator::{Bindings, Error, Profile, Source, Version, XML_DIR_VAR};
+use gl_generator::registry::Ns;
+
+const XML: &'static [u8] = include_bytes!("fixtures/profiles.xml");
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+}
+
+#[test]
+fn read_path() {
+    let source = Source::from(fixtures().join("profiles.xml"));
+    assert_eq!(&source.read().unwrap()[..], XML);
+
+    let registry = Bindings::new(Ns::Gl, Version::new(3, 2), Profile::Core)
+        .source(fixtures().join("profiles.xml"))
+        .registry()
+        .unwrap();
+    assert_eq!(registry.cmds.len(), 2);
+}
+
+#[test]
+fn read_missing_path() {
+    let path = fixtures().join("missing.xml");
+    match Bindings::new(Ns::Gl, Version::new(3, 2), Profile::Core).source(path.as_path()).registry() {
+        Err(Error::ReadRegistry { path: ref err_path, .. }) => assert_eq!(err_path, &path),
+        _ => panic!("`missing.xml` doesn't exist"),
+    }
+}
+
+// the only test that sets the variable, since the tests run in parallel
+#[test]
+fn xml_dir_override() {
+    env::remove_var(XML_DIR_VAR);
+    match Source::bundled("profiles.xml", b"bundled") {
+        Source::Bytes(bytes) => assert_eq!(bytes, b"bundled"),
+        Source::Path(_) => panic!("the bundled registry is used without the variable"),
+    }
+
+    env::set_var(XML_DIR_VAR, fixtures());
+    let source = Source::bundled("profiles.xml", b"bundled");
+    env::remove_var(XML_DIR_VAR);
+    match source {
+        Source::Path(ref path) => assert_eq!(path, &fixtures().join("profiles.xml")),
+        Source::Bytes(_) => panic!("the variable replaces the bundled registry"),
+    }
+    assert_eq!(&source.read().unwrap()[..], XML);
+}
@@ -0,0 +1,50 @@
+//! Checks the helpers that pass arrays to the vector equivalents of commands.
+
+extern crate gl_generator;
+
+use gl_generator::{Fallbacks, Version};
+use gl_generator::generators::{Generator, Options, WithOptions};
+use gl_generator::registry::{Ns, Registry};
+
+const XML: &'static [u8] = include_bytes!("fixtures/vec_helpers.xml");
+
+fn generate<G>(generator: G, vec_helpers: bool) -> String where G: Generator {
+    let mut dest = Vec::new();
+    let options = Options { vec_helpers: vec_helpers, .. Options::default() };
+    gl_generator::generate_bindings(WithOptions(generator, options), Ns::Gl, Fallbacks::All, XML,
+                                    vec![], Some(Version::new(1, 0)), "core", &mut dest).unwrap();
+    String::from_utf8(dest).unwrap()
+}
+
+#[test]
+fn vector_equivalents() {
+    let registry = Registry::from_xml(XML, Ns::Gl, None).unwrap();
+    assert_eq!(registry.vector_equivalent("Color3f").map(|c| &c.proto.ident[..]), Some("Color3fv"));
+    assert!(registry.vector_equivalent("Color3fv").is_none());
+    assert!(registry.vector_equivalent("Unknown").is_none());
+}
+
+#[test]
+fn global() {
+    let global = generate(gl_generator::GlobalGenerator, true);
+    assert!(global.contains("pub mod arrays"));
+    assert!(global.contains("/// Calls `Color3fv` with an array. Vector equivalent of `Color3f`."));
+    assert!(global.contains("pub unsafe fn Color3fv(v: &[types::GLfloat; 3]) -> () { super::Color3fv(v.as_ptr()) }"));
+    assert!(global.contains(
+        "pub unsafe fn VertexAttrib2fv(index: types::GLuint, v: &[types::GLfloat; 2]) -> () { \
+         super::VertexAttrib2fv(index, v.as_ptr()) }"));
+    // two arrays
+    assert!(!global.contains("pub unsafe fn Rectfv(v1: &"));
+}
+
+#[test]
+fn struct_() {
+    let struct_ = generate(gl_generator::StructGenerator, true);
+    assert!(struct_.contains(
+        "pub unsafe fn Color3fv(gl: &super::Gl, v: &[types::GLfloat; 3]) -> () { gl.Color3fv(v.as_ptr()) }"));
+}
+
+#[test]
+fn disabled() {
+    assert!(!generate(gl_generator::GlobalGenerator, false).contains("pub mod arrays"));
+}
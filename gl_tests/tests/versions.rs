@@ -0,0 +1,72 @@
+//! Checks how the requested version is parsed and matched against the features of a registry.
+
+extern crate gl_generator;
+
+mod common;
+
+use gl_generator::{Error, Profile, Version};
+use gl_generator::registry::{Ns, Registry};
+
+use common::cmds;
+
+const XML: &'static [u8] = include_bytes!("fixtures/versions.xml");
+
+fn filter(ns: Ns, version: Option<Version>) -> Result<Registry, Error> {
+    common::filter(XML, ns, version, Some(Profile::Core), Default::default())
+}
+
+#[test]
+fn parse_version() {
+    assert_eq!("4.5".parse::<Version>().unwrap(), Version::new(4, 5));
+    assert_eq!(Version::new(4, 5).to_string(), "4.5");
+    assert!("4.10".parse::<Version>().unwrap() > "4.9".parse::<Version>().unwrap());
+}
+
+#[test]
+fn parse_invalid_version() {
+    for &version in ["4.5.1", "4", "4.x", ""].iter() {
+        match version.parse::<Version>() {
+            Err(Error::InvalidVersion(ref invalid)) => assert_eq!(invalid, version),
+            _ => panic!("`{}` is not a version", version),
+        }
+    }
+}
+
+#[test]
+fn selects_features_up_to_the_version() {
+    let registry = filter(Ns::Gl, Some(Version::new(4, 9))).unwrap();
+    assert_eq!(cmds(&registry), ["Clear"]);
+    let registry = filter(Ns::Gl, Some(Version::new(4, 10))).unwrap();
+    assert_eq!(cmds(&registry), ["Clear", "Finish"]);
+}
+
+#[test]
+fn no_version_selects_the_latest() {
+    let registry = filter(Ns::Gl, None).unwrap();
+    assert_eq!(cmds(&registry), ["Clear", "Finish"]);
+}
+
+#[test]
+fn unknown_version() {
+    match filter(Ns::Gl, Some(Version::new(4, 5))) {
+        Err(Error::UnknownVersion { ref api, version, ref available }) => {
+            assert_eq!(api, "gl");
+            assert_eq!(version, Version::new(4, 5));
+            assert_eq!(available, &[Version::new(4, 9), Version::new(4, 10)]);
+        },
+        _ => panic!("4.5 is not in the registry"),
+    }
+}
+
+#[test]
+fn feature_covering_several_versions() {
+    for &version in [Version::new(1, 0), Version::new(1, 1)].iter() {
+        let registry = filter(Ns::Gles1, Some(version)).unwrap();
+        assert_eq!(cmds(&registry), ["Flush"]);
+    }
+    match filter(Ns::Gles1, Some(Version::new(1, 2))) {
+        Err(Error::UnknownVersion { ref available, .. }) =>
+            assert_eq!(available, &[Version::new(1, 0), Version::new(1, 1)]),
+        _ => panic!("1.2 is not in the registry"),
+    }
+}